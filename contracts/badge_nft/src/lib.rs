@@ -3,19 +3,616 @@
 //! NFTs minted when users complete quests
 
 #![no_std]
+#![allow(clippy::too_many_arguments)]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short,
+    contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short,
     Address, Env, Symbol, Map, Vec, String,
 };
 
+const DAY_IN_LEDGERS: u32 = 17280;
+const INSTANCE_BUMP_AMOUNT: u32 = 7 * DAY_IN_LEDGERS;
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+const PERSISTENT_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+/// Upper bound on the number of records returned by a paginated query
+const MAX_PAGE_SIZE: u32 = 50;
+
+/// Upper bound on achievement rules, all of which are checked on every mint and transfer
+const MAX_RULES: u32 = 20;
+
+/// Longest base URI accepted, leaving room for the badge ID in token URIs
+const MAX_BASE_URI_LEN: u32 = 200;
+
+/// Contract errors, codes are stable and surfaced to clients
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    BadgeAlreadyMinted = 1, // unused since badge IDs are assigned on mint
+    BadgeNotFound = 2,
+    NotOwner = 3,
+    NotInitialized = 4,
+    AlreadyInitialized = 5,
+    NoPendingAdmin = 6,
+    UnauthorizedMinter = 7,
+    MinterNotFound = 8,
+    ClassAlreadyExists = 9,
+    ClassNotFound = 10,
+    MaxSupplyReached = 11,
+    InvalidMaxSupply = 12,
+    Soulbound = 13,
+    BadgeRevoked = 14,
+    NotIssuer = 15,
+    InvalidExpiry = 16,
+    NotApproved = 17,
+    ExpirationInPast = 18,
+    MetadataFrozen = 19,
+    BaseUriNotSet = 20,
+    BaseUriTooLong = 21,
+    RecipeNotFound = 22,
+    RecipeAlreadyExists = 23,
+    RecipeDisabled = 24,
+    InvalidRecipe = 25,
+    RecipeMismatch = 26,
+    RuleNotFound = 27,
+    RuleAlreadyExists = 28,
+    InvalidRule = 29,
+    TooManyRules = 30,
+    QuestBadgeRestricted = 31,
+    IndexCorrupt = 32,
+}
+
+/// Persistent storage keys
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct Badge {
+pub enum DataKey {
+    /// Address approved by the admin to mint badges
+    Minter(Address),
+    /// Badge class definition
+    Class(Symbol),
+    /// Minted badge instance
+    Badge(u64),
+    /// Badge ID of a class's n-th minted instance
+    ClassBadge(Symbol, u32),
+    /// Reason and time a badge was revoked
+    Revocation(u64),
+    /// Address allowed to transfer a single badge (temporary storage)
+    Approval(u64),
+    /// Expiration ledger of an owner's collection-wide operator (temporary storage)
+    Operator(Address, Address),
+    /// Forge recipe
+    Recipe(Symbol),
+    /// Meta-achievement rule
+    Rule(Symbol),
+    /// Set once a user has been awarded a rule's badge
+    Achieved(Address, Symbol),
+    /// Address currently allowed to use a badge without owning it
+    Rental(u64),
+    /// Number of badges in an index
+    IndexLen(BadgeIndex),
+    /// Badge ID stored at a position of an index
+    IndexEntry(BadgeIndex, u32),
+    /// Position of a badge inside an index
+    IndexPos(BadgeIndex, u64),
+    /// Number of live badges of a class held by an owner
+    ClassHeld(Address, Symbol),
+    /// Number of live badges awarded by a quest held by an owner
+    QuestHeld(Address, Symbol),
+    /// Number of distinct quests an owner holds a live badge from
+    QuestsHeld(Address),
+}
+
+/// Enumerable sets of live badges, one ledger entry per badge
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BadgeIndex {
+    /// Every badge that is neither revoked nor burned
+    All,
+    /// Badges held by an owner
+    Owner(Address),
+    /// Badges awarded by a quest
+    Quest(Symbol),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+    Epic,
+    Legendary,
+}
+
+/// Display metadata shared by every badge of a class
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeMetadata {
+    pub name: String,
+    pub description: String,
+    pub image_uri: String,
+    pub rarity: Rarity,
+    pub attributes: Map<String, String>,
+}
+
+/// Badge design shared by every instance minted from it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeClass {
     pub id: Symbol,
-    pub quest_id: Symbol,
+    pub metadata: BadgeMetadata,
+    pub metadata_frozen: bool, // Set by the admin, metadata can no longer change
+    pub issuer: Address,
+    pub max_supply: Option<u32>,
+    pub supply: u32, // Badges currently in circulation
+    pub minted: u32, // Badges ever minted, also the last serial number
+    pub soulbound: bool, // Instances can only be moved by admin recovery
+    pub validity_period: Option<u64>, // Seconds an instance stays valid after minting
+    pub trusted: bool, // Issued by the admin or an approved minter, only then do its quest badges count for rules
+}
+
+/// Numbered instance of a badge class held by a user
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Badge {
+    pub id: u64,
+    pub class_id: Symbol,
+    pub serial: u32, // 1-based position within the class
+    pub quest_id: Option<Symbol>, // Quest that awarded the badge, only set by the quest platform
     pub owner: Address,
     pub minted_at: u64,
-    pub metadata: String, // JSON string with badge details
+    pub revoked: bool, // Details are kept under DataKey::Revocation
+    pub valid_until: Option<u64>, // Ledger timestamp after which the badge lapses
+}
+
+/// Why and when an issuer or admin revoked a badge
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Revocation {
+    pub reason: u32,
+    pub revoked_by: Address,
+    pub revoked_at: u64,
+}
+
+/// Combines badges of the input classes into one badge of the output class
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Recipe {
+    pub id: Symbol,
+    pub inputs: Map<Symbol, u32>, // Class ID to number of badges consumed
+    pub output: Symbol,
+    pub enabled: bool,
+}
+
+/// What a user's live badges must cover to earn a meta-achievement
+/// Lapsed badges still count until they are revoked or burned
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RuleCondition {
+    /// Badges of trusted classes awarded by at least this many distinct quests
+    DistinctQuests(u32),
+    /// At least one badge of every listed class
+    AllClasses(Vec<Symbol>),
+}
+
+/// Meta-achievement awarding one badge of the reward class per user
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AchievementRule {
+    pub id: Symbol,
+    pub condition: RuleCondition,
+    pub reward_class: Symbol,
+}
+
+/// Usage rights lent to another address until the expiry timestamp
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeRental {
+    pub user: Address,
+    pub expires: u64,
+}
+
+/// Spender allowed to move one badge until the expiration ledger
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeApproval {
+    pub spender: Address,
+    pub expiration_ledger: u32,
+}
+
+fn bump_instance(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+fn bump_persistent(env: &Env, key: &DataKey) {
+    env.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+fn read_admin(env: &Env) -> Result<Address, Error> {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("admin"))
+        .ok_or(Error::NotInitialized)
+}
+
+/// The registered quest platform is always allowed to mint
+fn is_quest_platform(env: &Env, minter: &Address) -> bool {
+    let quest_platform: Option<Address> = env.storage().instance().get(&symbol_short!("questpl"));
+    quest_platform.as_ref() == Some(minter)
+}
+
+fn is_approved_minter(env: &Env, minter: &Address) -> bool {
+    is_quest_platform(env, minter)
+        || env.storage().persistent().has(&DataKey::Minter(minter.clone()))
+}
+
+fn read_class(env: &Env, class_id: &Symbol) -> Option<BadgeClass> {
+    let key = DataKey::Class(class_id.clone());
+    let class = env.storage().persistent().get(&key);
+    if class.is_some() {
+        bump_persistent(env, &key);
+    }
+    class
+}
+
+fn write_class(env: &Env, class: &BadgeClass) {
+    let key = DataKey::Class(class.id.clone());
+    env.storage().persistent().set(&key, class);
+    bump_persistent(env, &key);
+}
+
+fn read_badge(env: &Env, badge_id: u64) -> Option<Badge> {
+    let key = DataKey::Badge(badge_id);
+    let badge = env.storage().persistent().get(&key);
+    if badge.is_some() {
+        bump_persistent(env, &key);
+    }
+    badge
+}
+
+fn write_badge(env: &Env, badge: &Badge) {
+    let key = DataKey::Badge(badge.id);
+    env.storage().persistent().set(&key, badge);
+    bump_persistent(env, &key);
+}
+
+fn index_len(env: &Env, index: &BadgeIndex) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::IndexLen(index.clone()))
+        .unwrap_or(0)
+}
+
+fn write_index_len(env: &Env, index: &BadgeIndex, len: u32) {
+    let key = DataKey::IndexLen(index.clone());
+    env.storage().persistent().set(&key, &len);
+    bump_persistent(env, &key);
+}
+
+fn write_index_entry(env: &Env, index: &BadgeIndex, pos: u32, badge_id: u64) {
+    let entry_key = DataKey::IndexEntry(index.clone(), pos);
+    env.storage().persistent().set(&entry_key, &badge_id);
+    bump_persistent(env, &entry_key);
+
+    let pos_key = DataKey::IndexPos(index.clone(), badge_id);
+    env.storage().persistent().set(&pos_key, &pos);
+    bump_persistent(env, &pos_key);
+}
+
+/// Append a badge to an index
+fn index_add(env: &Env, index: &BadgeIndex, badge_id: u64) {
+    let len = index_len(env, index);
+    write_index_entry(env, index, len, badge_id);
+    write_index_len(env, index, len + 1);
+}
+
+/// Remove a badge from an index by moving the last entry into its slot
+fn index_remove(env: &Env, index: &BadgeIndex, badge_id: u64) {
+    let pos_key = DataKey::IndexPos(index.clone(), badge_id);
+    let pos: u32 = match env.storage().persistent().get(&pos_key) {
+        Some(pos) => pos,
+        None => return,
+    };
+
+    let last = index_len(env, index)
+        .checked_sub(1)
+        .unwrap_or_else(|| panic_with_error!(env, Error::IndexCorrupt));
+    if pos != last {
+        let moved: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::IndexEntry(index.clone(), last))
+            .unwrap_or_else(|| panic_with_error!(env, Error::IndexCorrupt));
+        write_index_entry(env, index, pos, moved);
+    }
+
+    env.storage().persistent().remove(&DataKey::IndexEntry(index.clone(), last));
+    env.storage().persistent().remove(&pos_key);
+    write_index_len(env, index, last);
+}
+
+fn read_index_page(env: &Env, index: &BadgeIndex, offset: u32, limit: u32) -> Vec<u64> {
+    let mut page = Vec::new(env);
+    let end = offset.saturating_add(limit).min(index_len(env, index));
+    for pos in offset..end {
+        let key = DataKey::IndexEntry(index.clone(), pos);
+        if let Some(badge_id) = env.storage().persistent().get::<DataKey, u64>(&key) {
+            bump_persistent(env, &key);
+            page.push_back(badge_id);
+        }
+    }
+    page
+}
+
+fn read_count(env: &Env, key: &DataKey) -> u32 {
+    let count = env.storage().persistent().get(key);
+    if count.is_some() {
+        bump_persistent(env, key);
+    }
+    count.unwrap_or(0)
+}
+
+/// Step a holdings counter up or down, dropping it at zero, and return the new value
+fn step_count(env: &Env, key: &DataKey, up: bool) -> u32 {
+    let count = read_count(env, key);
+    let count = if up { count + 1 } else { count.saturating_sub(1) };
+    if count == 0 {
+        env.storage().persistent().remove(key);
+    } else {
+        env.storage().persistent().set(key, &count);
+        bump_persistent(env, key);
+    }
+    count
+}
+
+/// Keep an owner's per-class and per-quest counters in step with the badges they gain or lose
+/// Quest badges of untrusted classes are left out of the quest counters
+fn update_holdings(env: &Env, owner: &Address, badge: &Badge, gained: bool) {
+    step_count(env, &DataKey::ClassHeld(owner.clone(), badge.class_id.clone()), gained);
+    let Some(quest_id) = &badge.quest_id else {
+        return;
+    };
+    if !read_class(env, &badge.class_id).is_some_and(|class| class.trusted) {
+        return;
+    }
+    let held = step_count(env, &DataKey::QuestHeld(owner.clone(), quest_id.clone()), gained);
+    // Only the first badge gained from a quest or the last one lost changes the distinct count
+    if held == u32::from(gained) {
+        step_count(env, &DataKey::QuestsHeld(owner.clone()), gained);
+    }
+}
+
+/// Keep a temporary entry alive exactly as long as it is usable
+fn extend_until(env: &Env, key: &DataKey, expiration_ledger: u32) {
+    let live_for = expiration_ledger - env.ledger().sequence();
+    env.storage().temporary().extend_ttl(key, live_for, live_for);
+}
+
+fn read_approved(env: &Env, badge_id: u64) -> Option<Address> {
+    env.storage()
+        .temporary()
+        .get::<DataKey, BadgeApproval>(&DataKey::Approval(badge_id))
+        .filter(|approval| approval.expiration_ledger >= env.ledger().sequence())
+        .map(|approval| approval.spender)
+}
+
+fn is_operator(env: &Env, owner: &Address, operator: &Address) -> bool {
+    env.storage()
+        .temporary()
+        .get::<DataKey, u32>(&DataKey::Operator(owner.clone(), operator.clone()))
+        .is_some_and(|expiration_ledger| expiration_ledger >= env.ledger().sequence())
+}
+
+/// The owner, the badge's approved spender or one of the owner's operators
+fn can_manage(env: &Env, badge: &Badge, spender: &Address) -> bool {
+    *spender == badge.owner
+        || read_approved(env, badge.id).as_ref() == Some(spender)
+        || is_operator(env, &badge.owner, spender)
+}
+
+fn read_rental(env: &Env, badge_id: u64) -> Option<BadgeRental> {
+    env.storage()
+        .persistent()
+        .get::<DataKey, BadgeRental>(&DataKey::Rental(badge_id))
+        .filter(|rental| env.ledger().timestamp() <= rental.expires)
+}
+
+/// Revoked and soulbound badges cannot change hands
+fn check_transferable(env: &Env, badge: &Badge) -> Result<(), Error> {
+    if badge.revoked {
+        return Err(Error::BadgeRevoked);
+    }
+    let class = read_class(env, &badge.class_id).ok_or(Error::ClassNotFound)?;
+    if class.soulbound {
+        return Err(Error::Soulbound);
+    }
+    Ok(())
+}
+
+/// Live badges that have not lapsed are valid
+fn badge_is_valid(env: &Env, badge: &Badge) -> bool {
+    !badge.revoked
+        && badge
+            .valid_until
+            .is_none_or(|valid_until| env.ledger().timestamp() <= valid_until)
+}
+
+/// Take a badge out of the indexes and the supply counters
+fn retire_badge(env: &Env, badge: &Badge) {
+    index_remove(env, &BadgeIndex::Owner(badge.owner.clone()), badge.id);
+    update_holdings(env, &badge.owner, badge, false);
+    index_remove(env, &BadgeIndex::All, badge.id);
+    if let Some(quest_id) = &badge.quest_id {
+        index_remove(env, &BadgeIndex::Quest(quest_id.clone()), badge.id);
+    }
+    env.storage().temporary().remove(&DataKey::Approval(badge.id));
+    env.storage().persistent().remove(&DataKey::Rental(badge.id));
+
+    if let Some(mut class) = read_class(env, &badge.class_id) {
+        class.supply -= 1;
+        write_class(env, &class);
+    }
+
+    let count: i128 = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("badge_cnt"))
+        .unwrap_or(0);
+    env.storage().instance().set(&symbol_short!("badge_cnt"), &(count - 1));
+}
+
+/// Mint the next numbered instance of a class, enforcing its supply cap
+fn mint_instance(
+    env: &Env,
+    mut class: BadgeClass,
+    to: &Address,
+    quest_id: Option<Symbol>,
+) -> Result<u64, Error> {
+    if let Some(max) = class.max_supply {
+        if class.minted >= max {
+            return Err(Error::MaxSupplyReached);
+        }
+    }
+
+    let badge_id: u64 = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("badge_seq"))
+        .unwrap_or(0)
+        + 1;
+    env.storage().instance().set(&symbol_short!("badge_seq"), &badge_id);
+
+    class.supply += 1;
+    class.minted += 1;
+    let minted_at = env.ledger().timestamp();
+    let badge = Badge {
+        id: badge_id,
+        class_id: class.id.clone(),
+        serial: class.minted,
+        quest_id,
+        owner: to.clone(),
+        minted_at,
+        revoked: false,
+        valid_until: class
+            .validity_period
+            .map(|period| minted_at.saturating_add(period)),
+    };
+    write_badge(env, &badge);
+    write_class(env, &class);
+
+    let key = DataKey::ClassBadge(class.id.clone(), badge.serial);
+    env.storage().persistent().set(&key, &badge_id);
+    bump_persistent(env, &key);
+
+    index_add(env, &BadgeIndex::Owner(to.clone()), badge_id);
+    update_holdings(env, to, &badge, true);
+    index_add(env, &BadgeIndex::All, badge_id);
+    if let Some(quest_id) = &badge.quest_id {
+        index_add(env, &BadgeIndex::Quest(quest_id.clone()), badge_id);
+    }
+
+    let mut count: i128 = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("badge_cnt"))
+        .unwrap_or(0);
+    count += 1;
+    env.storage().instance().set(&symbol_short!("badge_cnt"), &count);
+
+    env.events().publish(
+        (symbol_short!("bdg_mint"), to.clone()),
+        (badge_id, class.id, badge.serial),
+    );
+
+    Ok(badge_id)
+}
+
+fn read_rule_ids(env: &Env) -> Vec<Symbol> {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("rules"))
+        .unwrap_or(Vec::new(env))
+}
+
+fn check_rule(env: &Env, condition: &RuleCondition, reward_class: &Symbol) -> Result<(), Error> {
+    let valid = match condition {
+        RuleCondition::DistinctQuests(count) => *count > 0,
+        RuleCondition::AllClasses(classes) => !classes.is_empty(),
+    };
+    if !valid {
+        return Err(Error::InvalidRule);
+    }
+    read_class(env, reward_class).ok_or(Error::ClassNotFound)?;
+    Ok(())
+}
+
+/// Mint the reward of every rule the user newly satisfies and return the new badge IDs
+/// Rules are checked in creation order, so a reward can count towards later rules
+/// Reads the user's holdings counters, never their badges, so the cost does not grow with holdings
+fn award_achievements(env: &Env, user: &Address) -> Vec<u64> {
+    let mut awarded = Vec::new(env);
+    let rule_ids = read_rule_ids(env);
+    if rule_ids.is_empty() {
+        return awarded;
+    }
+
+    for rule_id in rule_ids.iter() {
+        let achieved_key = DataKey::Achieved(user.clone(), rule_id.clone());
+        if env.storage().persistent().has(&achieved_key) {
+            continue;
+        }
+        let rule: AchievementRule = match env.storage().persistent().get(&DataKey::Rule(rule_id.clone())) {
+            Some(rule) => rule,
+            None => continue,
+        };
+        // Only the quest platform can tag a badge with a quest, so untagged badges never count
+        let met = match &rule.condition {
+            RuleCondition::DistinctQuests(count) => {
+                read_count(env, &DataKey::QuestsHeld(user.clone())) >= *count
+            }
+            RuleCondition::AllClasses(required) => required.iter().all(|class_id| {
+                read_count(env, &DataKey::ClassHeld(user.clone(), class_id)) > 0
+            }),
+        };
+        if !met {
+            continue;
+        }
+
+        // A sold-out reward class is retried on a later check
+        let reward = match read_class(env, &rule.reward_class) {
+            Some(class) => class,
+            None => continue,
+        };
+        if let Ok(badge_id) = mint_instance(env, reward, user, None) {
+            env.storage().persistent().set(&achieved_key, &true);
+            bump_persistent(env, &achieved_key);
+            awarded.push_back(badge_id);
+
+            env.events().publish(
+                (symbol_short!("achieved"), user.clone()),
+                (rule_id, badge_id),
+            );
+        }
+    }
+    awarded
+}
+
+/// Hand a badge to a new owner and keep both owners' indexes in sync
+/// Approvals and rentals granted by the previous owner are cleared
+fn move_badge(env: &Env, badge: &mut Badge, to: &Address) {
+    index_remove(env, &BadgeIndex::Owner(badge.owner.clone()), badge.id);
+    update_holdings(env, &badge.owner, badge, false);
+    env.storage().temporary().remove(&DataKey::Approval(badge.id));
+    env.storage().persistent().remove(&DataKey::Rental(badge.id));
+    index_add(env, &BadgeIndex::Owner(to.clone()), badge.id);
+    update_holdings(env, to, badge, true);
+    badge.owner = to.clone();
+    write_badge(env, badge);
 }
 
 #[contract]
@@ -24,76 +621,197 @@ pub struct BadgeNFT;
 #[contractimpl]
 impl BadgeNFT {
     /// Initialize the badge NFT system
-    pub fn initialize(env: Env, admin: Address, quest_platform: Address) {
+    pub fn initialize(env: Env, admin: Address, quest_platform: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&symbol_short!("init")) {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+
         env.storage().instance().set(&symbol_short!("admin"), &admin);
         env.storage().instance().set(&symbol_short!("questpl"), &quest_platform);
         env.storage().instance().set(&symbol_short!("init"), &true);
         env.storage().instance().set(&symbol_short!("badge_cnt"), &0i128);
+        bump_instance(&env);
+
+        Ok(())
     }
 
-    /// Mint a badge NFT to a user (called by quest platform when quest is completed)
-    pub fn mint_badge(
+    /// Create a badge class issued by the caller (admins and quest creators)
+    /// Classes of the admin and approved minters are trusted, anyone else's quests can be self-completed
+    pub fn create_class(
         env: Env,
-        to: Address,
-        badge_id: Symbol,
-        quest_id: Symbol,
-        metadata: String,
-    ) -> bool {
-        // Only quest platform can mint badges
-        // In production, the quest platform should call this with proper auth
-        // For now, we trust that only authorized addresses can call this
-
-        // Check if badge already minted
-        let mut badges: Map<Symbol, Badge> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("badges"))
-            .unwrap_or(Map::new(&env));
+        issuer: Address,
+        class_id: Symbol,
+        metadata: BadgeMetadata,
+        max_supply: Option<u32>,
+        soulbound: bool,
+        validity_period: Option<u64>,
+    ) -> Result<(), Error> {
+        issuer.require_auth();
+        let admin = read_admin(&env)?;
+        bump_instance(&env);
 
-        if badges.get(badge_id.clone()).is_some() {
-            panic!("Badge already minted");
+        if max_supply == Some(0) {
+            return Err(Error::InvalidMaxSupply);
+        }
+        if env.storage().persistent().has(&DataKey::Class(class_id.clone())) {
+            return Err(Error::ClassAlreadyExists);
         }
 
-        let current_time = env.ledger().timestamp();
-
-        let badge = Badge {
-            id: badge_id.clone(),
-            quest_id: quest_id.clone(),
-            owner: to.clone(),
-            minted_at: current_time,
-            metadata: metadata.clone(),
+        let class = BadgeClass {
+            id: class_id.clone(),
+            metadata,
+            metadata_frozen: false,
+            issuer: issuer.clone(),
+            max_supply,
+            supply: 0,
+            minted: 0,
+            soulbound,
+            validity_period,
+            trusted: issuer == admin || is_approved_minter(&env, &issuer),
         };
+        write_class(&env, &class);
 
-        badges.set(badge_id.clone(), badge);
+        env.events().publish(
+            (symbol_short!("cls_new"), issuer),
+            (class_id, max_supply),
+        );
 
-        // Track user's badges
-        let mut user_badges: Map<Address, Vec<Symbol>> = env
-            .storage()
-            .persistent()
-            .get(&symbol_short!("user_bdg"))
-            .unwrap_or(Map::new(&env));
+        Ok(())
+    }
 
-        let mut badges_list = user_badges.get(to.clone()).unwrap_or(Vec::new(&env));
-        badges_list.push_back(badge_id.clone());
-        user_badges.set(to.clone(), badges_list);
-        env.storage().persistent().set(&symbol_short!("user_bdg"), &user_badges);
+    /// Replace a class's metadata (class issuer or admin) until the admin freezes it
+    pub fn update_class_metadata(
+        env: Env,
+        caller: Address,
+        class_id: Symbol,
+        metadata: BadgeMetadata,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        bump_instance(&env);
 
-        env.storage().instance().set(&symbol_short!("badges"), &badges);
+        let mut class = read_class(&env, &class_id).ok_or(Error::ClassNotFound)?;
+        if caller != class.issuer && caller != read_admin(&env)? {
+            return Err(Error::NotIssuer);
+        }
+        if class.metadata_frozen {
+            return Err(Error::MetadataFrozen);
+        }
 
-        let mut count: i128 = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("badge_cnt"))
-            .unwrap_or(0);
-        count += 1;
-        env.storage().instance().set(&symbol_short!("badge_cnt"), &count);
+        class.metadata = metadata;
+        write_class(&env, &class);
+
+        env.events().publish(
+            (symbol_short!("meta_upd"), caller),
+            class_id,
+        );
+
+        Ok(())
+    }
+
+    /// Admin: Permanently lock a class's metadata
+    pub fn freeze_class_metadata(env: Env, class_id: Symbol) -> Result<(), Error> {
+        let admin = read_admin(&env)?;
+        admin.require_auth();
+        bump_instance(&env);
+
+        let mut class = read_class(&env, &class_id).ok_or(Error::ClassNotFound)?;
+        if class.metadata_frozen {
+            return Err(Error::MetadataFrozen);
+        }
+        class.metadata_frozen = true;
+        write_class(&env, &class);
+
+        env.events().publish(
+            (symbol_short!("meta_frz"), admin),
+            class_id,
+        );
+
+        Ok(())
+    }
+
+    /// Admin: Set the base URI that token URIs are built from
+    pub fn set_base_uri(env: Env, base_uri: String) -> Result<(), Error> {
+        let admin = read_admin(&env)?;
+        admin.require_auth();
+
+        if base_uri.len() > MAX_BASE_URI_LEN {
+            return Err(Error::BaseUriTooLong);
+        }
+        env.storage().instance().set(&symbol_short!("base_uri"), &base_uri);
+        bump_instance(&env);
 
         env.events().publish(
-            (symbol_short!("bdg_mint"), to),
-            (badge_id, quest_id),
+            (symbol_short!("base_uri"), admin),
+            base_uri,
         );
 
-        true
+        Ok(())
+    }
+
+    /// Get the base URI that token URIs are built from
+    pub fn get_base_uri(env: Env) -> Option<String> {
+        env.storage().instance().get(&symbol_short!("base_uri"))
+    }
+
+    /// Get a badge's metadata URI: the base URI followed by the badge ID
+    pub fn token_uri(env: Env, badge_id: u64) -> Result<String, Error> {
+        if !env.storage().persistent().has(&DataKey::Badge(badge_id)) {
+            return Err(Error::BadgeNotFound);
+        }
+        let base_uri: String = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("base_uri"))
+            .ok_or(Error::BaseUriNotSet)?;
+
+        let mut buf = [0u8; MAX_BASE_URI_LEN as usize + 20];
+        let base_len = base_uri.len() as usize;
+        base_uri.copy_into_slice(&mut buf[..base_len]);
+
+        let mut digits = [0u8; 20];
+        let mut len = 0;
+        let mut rest = badge_id;
+        loop {
+            digits[len] = b'0' + (rest % 10) as u8;
+            len += 1;
+            rest /= 10;
+            if rest == 0 {
+                break;
+            }
+        }
+        for i in 0..len {
+            buf[base_len + i] = digits[len - 1 - i];
+        }
+
+        Ok(String::from_bytes(&env, &buf[..base_len + len]))
+    }
+
+    /// Mint the next numbered instance of a class to a user and return its badge ID
+    /// The minter must be the class issuer, the quest platform or an admin-approved minter
+    /// Only the quest platform may set `quest_id`, everyone else mints with None
+    pub fn mint_badge(
+        env: Env,
+        minter: Address,
+        to: Address,
+        class_id: Symbol,
+        quest_id: Option<Symbol>,
+    ) -> Result<u64, Error> {
+        minter.require_auth();
+        bump_instance(&env);
+
+        let class = read_class(&env, &class_id).ok_or(Error::ClassNotFound)?;
+        if class.issuer != minter && !is_approved_minter(&env, &minter) {
+            return Err(Error::UnauthorizedMinter);
+        }
+        // Only the quest platform can vouch that a badge was earned in a quest
+        if quest_id.is_some() && !is_quest_platform(&env, &minter) {
+            return Err(Error::QuestBadgeRestricted);
+        }
+
+        let badge_id = mint_instance(&env, class, &to, quest_id)?;
+        award_achievements(&env, &to);
+        Ok(badge_id)
     }
 
     /// Transfer a badge to another address
@@ -101,94 +819,637 @@ impl BadgeNFT {
         env: Env,
         from: Address,
         to: Address,
-        badge_id: Symbol,
-    ) -> bool {
+        badge_id: u64,
+    ) -> Result<bool, Error> {
         from.require_auth();
+        bump_instance(&env);
 
-        let mut badges: Map<Symbol, Badge> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("badges"))
-            .unwrap_or(Map::new(&env));
-
-        let mut badge = badges.get(badge_id.clone()).unwrap();
+        let mut badge = read_badge(&env, badge_id).ok_or(Error::BadgeNotFound)?;
 
         if badge.owner != from {
-            panic!("Not the owner");
+            return Err(Error::NotOwner);
         }
+        check_transferable(&env, &badge)?;
 
-        badge.owner = to.clone();
-        badges.set(badge_id.clone(), badge);
+        move_badge(&env, &mut badge, &to);
+        award_achievements(&env, &to);
 
-        // Update user badges lists
-        let mut user_badges: Map<Address, Vec<Symbol>> = env
-            .storage()
-            .persistent()
-            .get(&symbol_short!("user_bdg"))
-            .unwrap_or(Map::new(&env));
-
-        // Remove from sender
-        if let Some(sender_badges) = user_badges.get(from.clone()) {
-            let mut new_badges = Vec::new(&env);
-            for i in 0..sender_badges.len() {
-                if sender_badges.get(i).unwrap() != badge_id {
-                    new_badges.push_back(sender_badges.get(i).unwrap());
-                }
-            }
-            if new_badges.len() > 0 {
-                user_badges.set(from.clone(), new_badges);
-            } else {
-                user_badges.remove(from.clone());
+        env.events().publish(
+            (symbol_short!("bdg_xfer"), from),
+            (badge_id, to),
+        );
+
+        Ok(true)
+    }
+
+    /// Allow a spender to transfer one badge until the expiration ledger
+    /// The approval is cleared when the badge changes hands
+    pub fn approve(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        badge_id: u64,
+        expiration_ledger: u32,
+    ) -> Result<(), Error> {
+        owner.require_auth();
+        bump_instance(&env);
+
+        let badge = read_badge(&env, badge_id).ok_or(Error::BadgeNotFound)?;
+        if badge.owner != owner {
+            return Err(Error::NotOwner);
+        }
+        check_transferable(&env, &badge)?;
+        if expiration_ledger < env.ledger().sequence() {
+            return Err(Error::ExpirationInPast);
+        }
+
+        let key = DataKey::Approval(badge_id);
+        let approval = BadgeApproval {
+            spender: spender.clone(),
+            expiration_ledger,
+        };
+        env.storage().temporary().set(&key, &approval);
+        extend_until(&env, &key, expiration_ledger);
+
+        env.events().publish(
+            (symbol_short!("approve"), owner, spender),
+            (badge_id, expiration_ledger),
+        );
+
+        Ok(())
+    }
+
+    /// Get the address approved to transfer a badge, if the approval is still live
+    pub fn get_approved(env: Env, badge_id: u64) -> Option<Address> {
+        read_approved(&env, badge_id)
+    }
+
+    /// Allow or stop an operator moving any of the owner's badges
+    pub fn set_approval_for_all(
+        env: Env,
+        owner: Address,
+        operator: Address,
+        approved: bool,
+        expiration_ledger: u32,
+    ) -> Result<(), Error> {
+        owner.require_auth();
+        bump_instance(&env);
+
+        let key = DataKey::Operator(owner.clone(), operator.clone());
+        if approved {
+            if expiration_ledger < env.ledger().sequence() {
+                return Err(Error::ExpirationInPast);
             }
+            env.storage().temporary().set(&key, &expiration_ledger);
+            extend_until(&env, &key, expiration_ledger);
+        } else {
+            env.storage().temporary().remove(&key);
         }
 
-        // Add to receiver
-        let mut receiver_badges = user_badges.get(to.clone()).unwrap_or(Vec::new(&env));
-        receiver_badges.push_back(badge_id.clone());
-        user_badges.set(to.clone(), receiver_badges);
+        env.events().publish(
+            (symbol_short!("appr_all"), owner, operator),
+            (approved, expiration_ledger),
+        );
+
+        Ok(())
+    }
+
+    /// Check whether an operator may move all of an owner's badges
+    pub fn is_approved_for_all(env: Env, owner: Address, operator: Address) -> bool {
+        is_operator(&env, &owner, &operator)
+    }
+
+    /// Transfer a badge on the owner's behalf (approved spender or operator)
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        badge_id: u64,
+    ) -> Result<(), Error> {
+        spender.require_auth();
+        bump_instance(&env);
 
-        env.storage().instance().set(&symbol_short!("badges"), &badges);
-        env.storage().persistent().set(&symbol_short!("user_bdg"), &user_badges);
+        let mut badge = read_badge(&env, badge_id).ok_or(Error::BadgeNotFound)?;
+        if badge.owner != from {
+            return Err(Error::NotOwner);
+        }
+        if !can_manage(&env, &badge, &spender) {
+            return Err(Error::NotApproved);
+        }
+        check_transferable(&env, &badge)?;
+
+        move_badge(&env, &mut badge, &to);
+        award_achievements(&env, &to);
 
         env.events().publish(
             (symbol_short!("bdg_xfer"), from),
             (badge_id, to),
         );
 
-        true
+        Ok(())
+    }
+
+    /// Lend a badge's usage rights until the expiry timestamp (owner or approved operator)
+    /// The rental is cleared when the badge changes hands
+    pub fn set_user(
+        env: Env,
+        caller: Address,
+        badge_id: u64,
+        user: Address,
+        expires: u64,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        bump_instance(&env);
+
+        let badge = read_badge(&env, badge_id).ok_or(Error::BadgeNotFound)?;
+        if !can_manage(&env, &badge, &caller) {
+            return Err(Error::NotApproved);
+        }
+        check_transferable(&env, &badge)?;
+        if expires <= env.ledger().timestamp() {
+            return Err(Error::InvalidExpiry);
+        }
+
+        let key = DataKey::Rental(badge_id);
+        let rental = BadgeRental {
+            user: user.clone(),
+            expires,
+        };
+        env.storage().persistent().set(&key, &rental);
+        bump_persistent(&env, &key);
+
+        env.events().publish(
+            (symbol_short!("bdg_user"), badge.owner),
+            (badge_id, user, expires),
+        );
+
+        Ok(())
+    }
+
+    /// End a rental early (owner or approved operator)
+    pub fn clear_user(env: Env, caller: Address, badge_id: u64) -> Result<(), Error> {
+        caller.require_auth();
+        bump_instance(&env);
+
+        let badge = read_badge(&env, badge_id).ok_or(Error::BadgeNotFound)?;
+        if !can_manage(&env, &badge, &caller) {
+            return Err(Error::NotApproved);
+        }
+        env.storage().persistent().remove(&DataKey::Rental(badge_id));
+
+        env.events().publish(
+            (symbol_short!("bdg_unuse"), badge.owner),
+            badge_id,
+        );
+
+        Ok(())
+    }
+
+    /// Get the address currently renting a badge, none once the rental expires
+    pub fn user_of(env: Env, badge_id: u64) -> Option<Address> {
+        read_rental(&env, badge_id).map(|rental| rental.user)
+    }
+
+    /// Get when the current rental of a badge expires
+    pub fn user_expires(env: Env, badge_id: u64) -> Option<u64> {
+        read_rental(&env, badge_id).map(|rental| rental.expires)
+    }
+
+    /// Admin: Move a badge to a new wallet of the same user, e.g. after a lost key
+    /// Works for soulbound badges too; the new wallet must consent
+    pub fn recover_badge(env: Env, badge_id: u64, to: Address) -> Result<(), Error> {
+        let admin = read_admin(&env)?;
+        admin.require_auth();
+        to.require_auth();
+        bump_instance(&env);
+
+        let mut badge = read_badge(&env, badge_id).ok_or(Error::BadgeNotFound)?;
+        if badge.revoked {
+            return Err(Error::BadgeRevoked);
+        }
+        let from = badge.owner.clone();
+        move_badge(&env, &mut badge, &to);
+        award_achievements(&env, &to);
+
+        env.events().publish(
+            (symbol_short!("bdg_rcvr"), admin),
+            (badge_id, from, to),
+        );
+
+        Ok(())
+    }
+
+    /// Revoke a badge (class issuer or admin), keeping it on record with the reason
+    pub fn revoke_badge(env: Env, caller: Address, badge_id: u64, reason: u32) -> Result<(), Error> {
+        caller.require_auth();
+        bump_instance(&env);
+
+        let mut badge = read_badge(&env, badge_id).ok_or(Error::BadgeNotFound)?;
+        if badge.revoked {
+            return Err(Error::BadgeRevoked);
+        }
+        let class = read_class(&env, &badge.class_id).ok_or(Error::ClassNotFound)?;
+        if caller != class.issuer && caller != read_admin(&env)? {
+            return Err(Error::NotIssuer);
+        }
+
+        retire_badge(&env, &badge);
+        badge.revoked = true;
+        write_badge(&env, &badge);
+
+        let key = DataKey::Revocation(badge_id);
+        let revocation = Revocation {
+            reason,
+            revoked_by: caller.clone(),
+            revoked_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&key, &revocation);
+        bump_persistent(&env, &key);
+
+        env.events().publish(
+            (symbol_short!("bdg_rvk"), caller),
+            (badge_id, badge.owner, reason),
+        );
+
+        Ok(())
     }
 
-    /// Get badge details
-    pub fn get_badge(env: Env, badge_id: Symbol) -> Option<Badge> {
-        let badges: Map<Symbol, Badge> = env
+    /// Admin: Define a recipe that forges the output class from input badges
+    pub fn create_recipe(
+        env: Env,
+        recipe_id: Symbol,
+        inputs: Map<Symbol, u32>,
+        output: Symbol,
+    ) -> Result<(), Error> {
+        let admin = read_admin(&env)?;
+        admin.require_auth();
+        bump_instance(&env);
+
+        let key = DataKey::Recipe(recipe_id.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(Error::RecipeAlreadyExists);
+        }
+        if inputs.is_empty() {
+            return Err(Error::InvalidRecipe);
+        }
+        for (class_id, count) in inputs.iter() {
+            if count == 0 {
+                return Err(Error::InvalidRecipe);
+            }
+            read_class(&env, &class_id).ok_or(Error::ClassNotFound)?;
+        }
+        read_class(&env, &output).ok_or(Error::ClassNotFound)?;
+
+        let recipe = Recipe {
+            id: recipe_id.clone(),
+            inputs,
+            output: output.clone(),
+            enabled: true,
+        };
+        env.storage().persistent().set(&key, &recipe);
+        bump_persistent(&env, &key);
+
+        let mut recipes: Vec<Symbol> = env
             .storage()
             .instance()
-            .get(&symbol_short!("badges"))
-            .unwrap_or(Map::new(&env));
+            .get(&symbol_short!("recipes"))
+            .unwrap_or(Vec::new(&env));
+        recipes.push_back(recipe_id.clone());
+        env.storage().instance().set(&symbol_short!("recipes"), &recipes);
+
+        env.events().publish(
+            (symbol_short!("rcp_new"), admin),
+            (recipe_id, output),
+        );
 
-        badges.get(badge_id)
+        Ok(())
     }
 
-    /// Get all badges owned by a user
-    pub fn get_user_badges(env: Env, user: Address) -> Vec<Symbol> {
-        let user_badges: Map<Address, Vec<Symbol>> = env
+    /// Admin: Enable or disable a recipe
+    pub fn set_recipe_enabled(env: Env, recipe_id: Symbol, enabled: bool) -> Result<(), Error> {
+        let admin = read_admin(&env)?;
+        admin.require_auth();
+        bump_instance(&env);
+
+        let key = DataKey::Recipe(recipe_id.clone());
+        let mut recipe: Recipe = env
             .storage()
             .persistent()
-            .get(&symbol_short!("user_bdg"))
-            .unwrap_or(Map::new(&env));
+            .get(&key)
+            .ok_or(Error::RecipeNotFound)?;
+        recipe.enabled = enabled;
+        env.storage().persistent().set(&key, &recipe);
+        bump_persistent(&env, &key);
+
+        env.events().publish(
+            (symbol_short!("rcp_set"), admin),
+            (recipe_id, enabled),
+        );
 
-        user_badges.get(user).unwrap_or(Vec::new(&env))
+        Ok(())
     }
 
-    /// Get badge owner
-    pub fn owner_of(env: Env, badge_id: Symbol) -> Option<Address> {
-        let badges: Map<Symbol, Badge> = env
-            .storage()
+    /// Get a recipe
+    pub fn get_recipe(env: Env, recipe_id: Symbol) -> Option<Recipe> {
+        env.storage().persistent().get(&DataKey::Recipe(recipe_id))
+    }
+
+    /// Get the IDs of all recipes, enabled or not
+    pub fn get_recipes(env: Env) -> Vec<Symbol> {
+        env.storage()
             .instance()
-            .get(&symbol_short!("badges"))
-            .unwrap_or(Map::new(&env));
+            .get(&symbol_short!("recipes"))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Burn exactly the badges a recipe asks for and mint its output to the owner
+    pub fn forge_badge(
+        env: Env,
+        owner: Address,
+        recipe_id: Symbol,
+        badge_ids: Vec<u64>,
+    ) -> Result<u64, Error> {
+        owner.require_auth();
+        bump_instance(&env);
+
+        let recipe: Recipe = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Recipe(recipe_id.clone()))
+            .ok_or(Error::RecipeNotFound)?;
+        if !recipe.enabled {
+            return Err(Error::RecipeDisabled);
+        }
+
+        // Tally the offered badges per class, rejecting anything the owner can't use
+        let mut offered: Map<Symbol, u32> = Map::new(&env);
+        let mut badges = Vec::new(&env);
+        for badge_id in badge_ids.iter() {
+            let badge = read_badge(&env, badge_id).ok_or(Error::BadgeNotFound)?;
+            if badge.owner != owner {
+                return Err(Error::NotOwner);
+            }
+            if !badge_is_valid(&env, &badge) {
+                return Err(Error::RecipeMismatch);
+            }
+            if badges.iter().any(|seen: Badge| seen.id == badge_id) {
+                return Err(Error::RecipeMismatch);
+            }
+            let count = offered.get(badge.class_id.clone()).unwrap_or(0);
+            offered.set(badge.class_id.clone(), count + 1);
+            badges.push_back(badge);
+        }
+        if offered != recipe.inputs {
+            return Err(Error::RecipeMismatch);
+        }
+
+        for badge in badges.iter() {
+            retire_badge(&env, &badge);
+            env.storage().persistent().remove(&DataKey::Badge(badge.id));
+        }
+
+        let output = read_class(&env, &recipe.output).ok_or(Error::ClassNotFound)?;
+        let badge_id = mint_instance(&env, output, &owner, None)?;
+        award_achievements(&env, &owner);
+
+        env.events().publish(
+            (symbol_short!("bdg_forge"), owner),
+            (recipe_id, badge_ids, badge_id),
+        );
+
+        Ok(badge_id)
+    }
+
+    /// Admin: Add a meta-achievement rule
+    pub fn create_rule(
+        env: Env,
+        rule_id: Symbol,
+        condition: RuleCondition,
+        reward_class: Symbol,
+    ) -> Result<(), Error> {
+        let admin = read_admin(&env)?;
+        admin.require_auth();
+        bump_instance(&env);
+
+        let key = DataKey::Rule(rule_id.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(Error::RuleAlreadyExists);
+        }
+        let mut rule_ids = read_rule_ids(&env);
+        if rule_ids.len() >= MAX_RULES {
+            return Err(Error::TooManyRules);
+        }
+        check_rule(&env, &condition, &reward_class)?;
+
+        let rule = AchievementRule {
+            id: rule_id.clone(),
+            condition,
+            reward_class,
+        };
+        env.storage().persistent().set(&key, &rule);
+        bump_persistent(&env, &key);
+        rule_ids.push_back(rule_id.clone());
+        env.storage().instance().set(&symbol_short!("rules"), &rule_ids);
+
+        env.events().publish(
+            (symbol_short!("rule_new"), admin),
+            rule_id,
+        );
+
+        Ok(())
+    }
+
+    /// Admin: Change a rule's condition or reward; users already awarded keep their badge
+    pub fn update_rule(
+        env: Env,
+        rule_id: Symbol,
+        condition: RuleCondition,
+        reward_class: Symbol,
+    ) -> Result<(), Error> {
+        let admin = read_admin(&env)?;
+        admin.require_auth();
+        bump_instance(&env);
+
+        let key = DataKey::Rule(rule_id.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(Error::RuleNotFound);
+        }
+        check_rule(&env, &condition, &reward_class)?;
+
+        let rule = AchievementRule {
+            id: rule_id.clone(),
+            condition,
+            reward_class,
+        };
+        env.storage().persistent().set(&key, &rule);
+        bump_persistent(&env, &key);
+
+        env.events().publish(
+            (symbol_short!("rule_upd"), admin),
+            rule_id,
+        );
+
+        Ok(())
+    }
+
+    /// Admin: Remove a rule
+    pub fn delete_rule(env: Env, rule_id: Symbol) -> Result<(), Error> {
+        let admin = read_admin(&env)?;
+        admin.require_auth();
+        bump_instance(&env);
+
+        let key = DataKey::Rule(rule_id.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(Error::RuleNotFound);
+        }
+        env.storage().persistent().remove(&key);
+
+        let mut rule_ids = read_rule_ids(&env);
+        if let Some(pos) = rule_ids.first_index_of(&rule_id) {
+            rule_ids.remove(pos);
+            env.storage().instance().set(&symbol_short!("rules"), &rule_ids);
+        }
+
+        env.events().publish(
+            (symbol_short!("rule_del"), admin),
+            rule_id,
+        );
+
+        Ok(())
+    }
+
+    /// Get a meta-achievement rule
+    pub fn get_rule(env: Env, rule_id: Symbol) -> Option<AchievementRule> {
+        env.storage().persistent().get(&DataKey::Rule(rule_id))
+    }
+
+    /// Get the IDs of all rules in the order they are checked
+    pub fn get_rules(env: Env) -> Vec<Symbol> {
+        read_rule_ids(&env)
+    }
+
+    /// Award any meta-achievements a user already qualifies for, e.g. after a new rule
+    /// Returns the IDs of the badges minted
+    pub fn check_achievements(env: Env, user: Address) -> Vec<u64> {
+        bump_instance(&env);
+        award_achievements(&env, &user)
+    }
+
+    /// Check whether a user has been awarded a rule's badge
+    pub fn has_achievement(env: Env, user: Address, rule_id: Symbol) -> bool {
+        env.storage().persistent().has(&DataKey::Achieved(user, rule_id))
+    }
+
+    /// Burn a badge (owner only)
+    pub fn burn_badge(env: Env, owner: Address, badge_id: u64) -> Result<(), Error> {
+        owner.require_auth();
+        bump_instance(&env);
+
+        let badge = read_badge(&env, badge_id).ok_or(Error::BadgeNotFound)?;
+        if badge.owner != owner {
+            return Err(Error::NotOwner);
+        }
+        if badge.revoked {
+            return Err(Error::BadgeRevoked);
+        }
+
+        retire_badge(&env, &badge);
+        env.storage().persistent().remove(&DataKey::Badge(badge_id));
+
+        env.events().publish(
+            (symbol_short!("bdg_burn"), owner),
+            badge_id,
+        );
+
+        Ok(())
+    }
+
+    /// Extend a badge's validity (class issuer only)
+    pub fn renew_badge(env: Env, issuer: Address, badge_id: u64, valid_until: u64) -> Result<(), Error> {
+        issuer.require_auth();
+        bump_instance(&env);
+
+        let mut badge = read_badge(&env, badge_id).ok_or(Error::BadgeNotFound)?;
+        if badge.revoked {
+            return Err(Error::BadgeRevoked);
+        }
+        let class = read_class(&env, &badge.class_id).ok_or(Error::ClassNotFound)?;
+        if issuer != class.issuer {
+            return Err(Error::NotIssuer);
+        }
+        if valid_until <= env.ledger().timestamp() {
+            return Err(Error::InvalidExpiry);
+        }
+
+        badge.valid_until = Some(valid_until);
+        write_badge(&env, &badge);
+
+        env.events().publish(
+            (symbol_short!("bdg_renew"), issuer),
+            (badge_id, valid_until),
+        );
+
+        Ok(())
+    }
+
+    /// Check whether a badge exists, is not revoked and has not lapsed
+    pub fn is_valid(env: Env, badge_id: u64) -> bool {
+        read_badge(&env, badge_id).is_some_and(|badge| badge_is_valid(&env, &badge))
+    }
+
+    /// Get badge details, including revoked badges
+    pub fn get_badge(env: Env, badge_id: u64) -> Option<Badge> {
+        read_badge(&env, badge_id)
+    }
+
+    /// Get the badges owned by a user (paginated)
+    pub fn badges_of(env: Env, owner: Address, offset: u32, limit: u32) -> Vec<u64> {
+        read_index_page(&env, &BadgeIndex::Owner(owner), offset, limit.min(MAX_PAGE_SIZE))
+    }
+
+    /// Get the number of badges owned by a user
+    pub fn balance_of(env: Env, owner: Address) -> u32 {
+        index_len(&env, &BadgeIndex::Owner(owner))
+    }
+
+    /// Get the badge at a position of the list of all live badges
+    pub fn badge_by_index(env: Env, index: u32) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::IndexEntry(BadgeIndex::All, index))
+    }
+
+    /// Get the badges awarded by a quest (paginated)
+    pub fn badges_for_quest(env: Env, quest_id: Symbol, offset: u32, limit: u32) -> Vec<u64> {
+        read_index_page(&env, &BadgeIndex::Quest(quest_id), offset, limit.min(MAX_PAGE_SIZE))
+    }
+
+    /// Get why and when a badge was revoked
+    pub fn get_revocation(env: Env, badge_id: u64) -> Option<Revocation> {
+        env.storage().persistent().get(&DataKey::Revocation(badge_id))
+    }
+
+    /// Get the valid badges among a page of the badges owned by a user
+    /// Lapsed badges are left out, so a page can be shorter than `limit` before the end
+    pub fn get_user_valid_badges(env: Env, user: Address, offset: u32, limit: u32) -> Vec<u64> {
+        let mut valid = Vec::new(&env);
+        let page = read_index_page(&env, &BadgeIndex::Owner(user), offset, limit.min(MAX_PAGE_SIZE));
+        for badge_id in page.iter() {
+            if let Some(badge) = read_badge(&env, badge_id) {
+                if badge_is_valid(&env, &badge) {
+                    valid.push_back(badge_id);
+                }
+            }
+        }
+        valid
+    }
 
-        badges.get(badge_id).map(|badge| badge.owner)
+    /// Get badge owner, none once the badge is revoked or burned
+    pub fn owner_of(env: Env, badge_id: u64) -> Option<Address> {
+        read_badge(&env, badge_id)
+            .filter(|badge| !badge.revoked)
+            .map(|badge| badge.owner)
     }
 
     /// Get total badge count
@@ -198,6 +1459,151 @@ impl BadgeNFT {
             .get(&symbol_short!("badge_cnt"))
             .unwrap_or(0)
     }
+
+    /// Get a badge class definition
+    pub fn get_class(env: Env, class_id: Symbol) -> Option<BadgeClass> {
+        read_class(&env, &class_id)
+    }
+
+    /// Get the issuer of a badge class
+    pub fn class_issuer(env: Env, class_id: Symbol) -> Option<Address> {
+        read_class(&env, &class_id).map(|class| class.issuer)
+    }
+
+    /// Get the number of badges of a class currently in circulation
+    pub fn class_supply(env: Env, class_id: Symbol) -> Result<u32, Error> {
+        read_class(&env, &class_id)
+            .map(|class| class.supply)
+            .ok_or(Error::ClassNotFound)
+    }
+
+    /// Get the holders of a class's instances in mint order, one entry per badge (paginated)
+    /// Pages cover serial numbers, so revoked and burned badges leave gaps
+    pub fn class_holders(
+        env: Env,
+        class_id: Symbol,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<Address>, Error> {
+        let class = read_class(&env, &class_id).ok_or(Error::ClassNotFound)?;
+
+        let mut holders = Vec::new(&env);
+        let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(class.minted);
+        for serial in offset..end {
+            let key = DataKey::ClassBadge(class_id.clone(), serial + 1);
+            if let Some(badge_id) = env.storage().persistent().get::<DataKey, u64>(&key) {
+                if let Some(badge) = read_badge(&env, badge_id).filter(|b| !b.revoked) {
+                    holders.push_back(badge.owner);
+                }
+            }
+        }
+        Ok(holders)
+    }
+
+    /// Admin: Approve an additional address to mint badges
+    pub fn add_minter(env: Env, minter: Address) -> Result<(), Error> {
+        let admin = read_admin(&env)?;
+        admin.require_auth();
+
+        env.storage().persistent().set(&DataKey::Minter(minter.clone()), &true);
+
+        env.events().publish(
+            (symbol_short!("mntr_add"), admin),
+            minter,
+        );
+
+        Ok(())
+    }
+
+    /// Admin: Revoke an address's permission to mint badges
+    pub fn remove_minter(env: Env, minter: Address) -> Result<(), Error> {
+        let admin = read_admin(&env)?;
+        admin.require_auth();
+
+        let key = DataKey::Minter(minter.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(Error::MinterNotFound);
+        }
+        env.storage().persistent().remove(&key);
+
+        env.events().publish(
+            (symbol_short!("mntr_rm"), admin),
+            minter,
+        );
+
+        Ok(())
+    }
+
+    /// Check whether an address may mint badges
+    pub fn is_minter(env: Env, minter: Address) -> bool {
+        is_approved_minter(&env, &minter)
+    }
+
+    /// Admin: Propose a new admin, who must accept before taking over
+    pub fn propose_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        let admin = read_admin(&env)?;
+        admin.require_auth();
+
+        env.storage().instance().set(&symbol_short!("pend_adm"), &new_admin);
+
+        env.events().publish(
+            (symbol_short!("adm_prop"), admin),
+            new_admin,
+        );
+
+        Ok(())
+    }
+
+    /// Accept a pending admin transfer (called by the proposed admin)
+    pub fn accept_admin(env: Env) -> Result<(), Error> {
+        let admin = read_admin(&env)?;
+        let new_admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("pend_adm"))
+            .ok_or(Error::NoPendingAdmin)?;
+        new_admin.require_auth();
+
+        env.storage().instance().set(&symbol_short!("admin"), &new_admin);
+        env.storage().instance().remove(&symbol_short!("pend_adm"));
+
+        env.events().publish(
+            (symbol_short!("adm_acc"), admin),
+            new_admin,
+        );
+
+        Ok(())
+    }
+
+    /// Admin: Cancel a pending admin transfer
+    pub fn cancel_admin_transfer(env: Env) -> Result<(), Error> {
+        let admin = read_admin(&env)?;
+        admin.require_auth();
+
+        let pending: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("pend_adm"))
+            .ok_or(Error::NoPendingAdmin)?;
+        env.storage().instance().remove(&symbol_short!("pend_adm"));
+
+        env.events().publish(
+            (symbol_short!("adm_cncl"), admin),
+            pending,
+        );
+
+        Ok(())
+    }
+
+    /// Get current admin
+    pub fn get_admin(env: Env) -> Result<Address, Error> {
+        read_admin(&env)
+    }
+
+    /// Get the proposed admin awaiting acceptance
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("pend_adm"))
+    }
 }
 
 #[cfg(test)]
@@ -1,11 +1,38 @@
 #![cfg(test)]
 
-use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env, Symbol, String};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    vec, Address, Env, IntoVal, Map, String, Vec,
+};
 use super::*;
 
+fn badge_metadata(env: &Env, name: &str) -> BadgeMetadata {
+    BadgeMetadata {
+        name: String::from_str(env, name),
+        description: String::from_str(env, "Awarded for completing a quest"),
+        image_uri: String::from_str(env, "ipfs://badge-image"),
+        rarity: Rarity::Common,
+        attributes: Map::new(env),
+    }
+}
+
+fn create_test_class(env: &Env, client: &BadgeNFTClient, issuer: &Address, class_id: &Symbol) {
+    client.create_class(
+        issuer,
+        class_id,
+        &badge_metadata(env, "First Quest Badge"),
+        &None,
+        &false,
+        &None,
+    );
+}
+
 #[test]
 fn test_initialize() {
     let env = Env::default();
+    env.mock_all_auths();
+
     let admin = Address::generate(&env);
     let quest_platform = Address::generate(&env);
     
@@ -20,6 +47,8 @@ fn test_initialize() {
 #[test]
 fn test_mint_badge() {
     let env = Env::default();
+    env.mock_all_auths();
+
     let admin = Address::generate(&env);
     let quest_platform = Address::generate(&env);
     let user = Address::generate(&env);
@@ -29,17 +58,1170 @@ fn test_mint_badge() {
     
     client.initialize(&admin, &quest_platform);
     
-    let badge_id = symbol_short!("badge1");
+    let class_id = symbol_short!("explorer");
     let quest_id = symbol_short!("quest1");
-    let metadata = String::from_str(&env, r#"{"name":"First Quest Badge","image":"ipfs://..."}"#);
+    create_test_class(&env, &client, &admin, &class_id);
     
     // This would be called by quest platform in production
     // For test, we'll simulate by calling from quest_platform address
-    client.mint_badge(&quest_platform, &user, &badge_id, &quest_id, &metadata);
+    let badge_id = client.mint_badge(&quest_platform, &user, &class_id, &Some(quest_id.clone()));
     
     let badge = client.get_badge(&badge_id).unwrap();
     assert_eq!(badge.owner, user);
-    assert_eq!(badge.quest_id, quest_id);
+    assert_eq!(badge.class_id, class_id);
+    assert_eq!(badge.serial, 1);
+    assert_eq!(badge.quest_id, Some(quest_id));
+    assert_eq!(client.total_badges(), 1);
+    assert_eq!(client.class_supply(&class_id), 1);
+}
+
+#[test]
+fn test_transfer_badge() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let quest_platform = Address::generate(&env);
+    let user = Address::generate(&env);
+    let friend = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, BadgeNFT);
+    let client = BadgeNFTClient::new(&env, &contract_id);
+    client.initialize(&admin, &quest_platform);
+
+    let class_id = symbol_short!("explorer");
+    create_test_class(&env, &client, &admin, &class_id);
+    let badge_id = client.mint_badge(&quest_platform, &user, &class_id, &None);
+    client.transfer_badge(&user, &friend, &badge_id);
+
+    assert_eq!(client.owner_of(&badge_id), Some(friend.clone()));
+    assert_eq!(client.badges_of(&user, &0, &50).len(), 0);
+    assert_eq!(client.badges_of(&friend, &0, &50), Vec::from_array(&env, [badge_id]));
+}
+
+#[test]
+fn test_badge_errors() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let quest_platform = Address::generate(&env);
+    let user = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, BadgeNFT);
+    let client = BadgeNFTClient::new(&env, &contract_id);
+    client.initialize(&admin, &quest_platform);
+
+    let class_id = symbol_short!("explorer");
+    create_test_class(&env, &client, &admin, &class_id);
+    let badge_id = client.mint_badge(&quest_platform, &user, &class_id, &None);
+
+    assert_eq!(
+        client.try_transfer_badge(&stranger, &user, &badge_id),
+        Err(Ok(Error::NotOwner))
+    );
+    assert_eq!(
+        client.try_transfer_badge(&user, &stranger, &99),
+        Err(Ok(Error::BadgeNotFound))
+    );
+}
+
+#[test]
+fn test_initialize_only_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    let quest_platform = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, BadgeNFT);
+    let client = BadgeNFTClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &quest_platform);
+    assert_eq!(env.auths()[0].0, admin);
+
+    assert_eq!(
+        client.try_initialize(&attacker, &quest_platform),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    assert_eq!(client.get_admin(), admin);
+}
+
+#[test]
+fn test_two_step_admin_handover() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let other = Address::generate(&env);
+    let quest_platform = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, BadgeNFT);
+    let client = BadgeNFTClient::new(&env, &contract_id);
+
+    assert_eq!(client.try_propose_admin(&new_admin), Err(Ok(Error::NotInitialized)));
+    client.initialize(&admin, &quest_platform);
+    assert_eq!(client.try_accept_admin(), Err(Ok(Error::NoPendingAdmin)));
+
+    client.propose_admin(&new_admin);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+    assert_eq!(client.get_admin(), admin);
+
+    client.accept_admin();
+    assert_eq!(env.auths()[0].0, new_admin);
+    assert_eq!(client.get_admin(), new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+
+    client.propose_admin(&other);
+    assert_eq!(env.auths()[0].0, new_admin);
+    client.cancel_admin_transfer();
+    assert_eq!(client.get_pending_admin(), None);
+    assert_eq!(client.try_accept_admin(), Err(Ok(Error::NoPendingAdmin)));
+    assert_eq!(client.try_cancel_admin_transfer(), Err(Ok(Error::NoPendingAdmin)));
+    assert_eq!(client.get_admin(), new_admin);
+}
+
+#[test]
+fn test_unauthorized_mint_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let quest_platform = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, BadgeNFT);
+    let client = BadgeNFTClient::new(&env, &contract_id);
+    client.initialize(&admin, &quest_platform);
+
+    let class_id = symbol_short!("explorer");
+    create_test_class(&env, &client, &admin, &class_id);
+
+    assert_eq!(
+        client.try_mint_badge(&stranger, &user, &class_id, &None),
+        Err(Ok(Error::UnauthorizedMinter))
+    );
+    assert_eq!(client.total_badges(), 0);
+
+    // The quest platform itself must sign the mint
+    env.set_auths(&[]);
+    assert!(client
+        .try_mint_badge(&quest_platform, &user, &class_id, &None)
+        .is_err());
+    assert_eq!(client.total_badges(), 0);
+
+    env.mock_all_auths();
+    let badge_id = client.mint_badge(&quest_platform, &user, &class_id, &None);
+    assert_eq!(env.auths()[0].0, quest_platform);
+    assert_eq!(client.owner_of(&badge_id), Some(user));
+}
+
+#[test]
+fn test_admin_managed_minters() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let quest_platform = Address::generate(&env);
+    let game_server = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, BadgeNFT);
+    let client = BadgeNFTClient::new(&env, &contract_id);
+    client.initialize(&admin, &quest_platform);
+
+    let class_id = symbol_short!("explorer");
+    create_test_class(&env, &client, &admin, &class_id);
+
+    assert!(client.is_minter(&quest_platform));
+    assert!(!client.is_minter(&game_server));
+
+    client.add_minter(&game_server);
+    assert_eq!(env.auths()[0].0, admin);
+    assert!(client.is_minter(&game_server));
+    client.mint_badge(&game_server, &user, &class_id, &None);
+    assert_eq!(client.badges_of(&user, &0, &50).len(), 1);
+
+    // Only the quest platform may tag a badge with the quest that awarded it
+    let quest_id = Some(symbol_short!("quest1"));
+    assert_eq!(
+        client.try_mint_badge(&game_server, &user, &class_id, &quest_id),
+        Err(Ok(Error::QuestBadgeRestricted))
+    );
+    assert_eq!(
+        client.try_mint_badge(&admin, &user, &class_id, &quest_id),
+        Err(Ok(Error::QuestBadgeRestricted))
+    );
+    assert_eq!(client.badges_of(&user, &0, &50).len(), 1);
+
+    client.remove_minter(&game_server);
+    assert!(!client.is_minter(&game_server));
+    assert_eq!(
+        client.try_mint_badge(&game_server, &user, &class_id, &None),
+        Err(Ok(Error::UnauthorizedMinter))
+    );
+    assert_eq!(client.try_remove_minter(&game_server), Err(Ok(Error::MinterNotFound)));
+}
+
+#[test]
+fn test_badge_classes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let quest_platform = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, BadgeNFT);
+    let client = BadgeNFTClient::new(&env, &contract_id);
+    client.initialize(&admin, &quest_platform);
+
+    // Quest creators issue their own classes
+    let class_id = symbol_short!("dragon");
+    client.create_class(
+        &creator,
+        &class_id,
+        &badge_metadata(&env, "Dragon Slayer"),
+        &Some(2),
+        &false,
+        &None,
+    );
+    assert_eq!(env.auths()[0].0, creator);
+
+    let class = client.get_class(&class_id).unwrap();
+    assert_eq!(class.issuer, creator);
+    assert_eq!(class.max_supply, Some(2));
+    assert!(!class.trusted);
+    assert_eq!(class.supply, 0);
+    assert_eq!(client.class_issuer(&class_id), Some(creator.clone()));
+
+    // Many users hold numbered instances of the same class
+    let first = client.mint_badge(&quest_platform, &user1, &class_id, &None);
+    let second = client.mint_badge(&creator, &user2, &class_id, &None);
+    assert_ne!(first, second);
+    assert_eq!(client.get_badge(&first).unwrap().serial, 1);
+    assert_eq!(client.get_badge(&second).unwrap().serial, 2);
+    assert_eq!(client.class_supply(&class_id), 2);
+    assert_eq!(
+        client.class_holders(&class_id, &0, &10),
+        Vec::from_array(&env, [user1.clone(), user2.clone()])
+    );
+    assert_eq!(
+        client.class_holders(&class_id, &1, &10),
+        Vec::from_array(&env, [user2.clone()])
+    );
+
+    client.transfer_badge(&user1, &user2, &first);
+    assert_eq!(
+        client.class_holders(&class_id, &0, &10),
+        Vec::from_array(&env, [user2.clone(), user2.clone()])
+    );
+
+    assert_eq!(
+        client.try_mint_badge(&quest_platform, &user1, &class_id, &None),
+        Err(Ok(Error::MaxSupplyReached))
+    );
+    assert_eq!(
+        client.try_create_class(
+            &admin,
+            &class_id,
+            &badge_metadata(&env, "Copy"),
+            &None,
+            &false,
+            &None,
+        ),
+        Err(Ok(Error::ClassAlreadyExists))
+    );
+    assert_eq!(
+        client.try_create_class(
+            &admin,
+            &symbol_short!("empty"),
+            &badge_metadata(&env, "Empty"),
+            &Some(0),
+            &false,
+            &None,
+        ),
+        Err(Ok(Error::InvalidMaxSupply))
+    );
+    assert_eq!(
+        client.try_mint_badge(&quest_platform, &user1, &symbol_short!("missing"), &None),
+        Err(Ok(Error::ClassNotFound))
+    );
+    assert_eq!(client.try_class_supply(&symbol_short!("missing")), Err(Ok(Error::ClassNotFound)));
+}
+
+#[test]
+fn test_soulbound_badges() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let quest_platform = Address::generate(&env);
+    let user = Address::generate(&env);
+    let friend = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, BadgeNFT);
+    let client = BadgeNFTClient::new(&env, &contract_id);
+    client.initialize(&admin, &quest_platform);
+
+    let class_id = symbol_short!("proof");
+    client.create_class(
+        &admin,
+        &class_id,
+        &badge_metadata(&env, "Proof of Completion"),
+        &None,
+        &true,
+        &None,
+    );
+    assert!(client.get_class(&class_id).unwrap().soulbound);
+
+    let badge_id = client.mint_badge(&quest_platform, &user, &class_id, &None);
+    assert_eq!(
+        client.try_transfer_badge(&user, &friend, &badge_id),
+        Err(Ok(Error::Soulbound))
+    );
+    assert_eq!(client.owner_of(&badge_id), Some(user.clone()));
+
+    // Transferable classes are unaffected
+    let tradable = symbol_short!("explorer");
+    create_test_class(&env, &client, &admin, &tradable);
+    let other_id = client.mint_badge(&quest_platform, &user, &tradable, &None);
+    client.transfer_badge(&user, &friend, &other_id);
+    assert_eq!(client.owner_of(&other_id), Some(friend));
+}
+
+#[test]
+fn test_admin_recovers_soulbound_badge() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let quest_platform = Address::generate(&env);
+    let old_wallet = Address::generate(&env);
+    let new_wallet = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, BadgeNFT);
+    let client = BadgeNFTClient::new(&env, &contract_id);
+    client.initialize(&admin, &quest_platform);
+
+    let class_id = symbol_short!("proof");
+    client.create_class(
+        &admin,
+        &class_id,
+        &badge_metadata(&env, "Proof of Completion"),
+        &None,
+        &true,
+        &None,
+    );
+    let badge_id = client.mint_badge(&quest_platform, &old_wallet, &class_id, &None);
+
+    client.recover_badge(&badge_id, &new_wallet);
+    let auths = env.auths();
+    assert_eq!(auths.len(), 2);
+    assert_eq!(auths[0].0, admin);
+    assert_eq!(auths[1].0, new_wallet);
+
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("bdg_rcvr"), admin.clone()).into_val(&env),
+                (badge_id, old_wallet.clone(), new_wallet.clone()).into_val(&env),
+            ),
+        ]
+    );
+
+    assert_eq!(client.owner_of(&badge_id), Some(new_wallet.clone()));
+    assert_eq!(client.badges_of(&old_wallet, &0, &50).len(), 0);
+    assert_eq!(client.badges_of(&new_wallet, &0, &50), Vec::from_array(&env, [badge_id]));
+
+    // Still soulbound in the new wallet
+    assert_eq!(
+        client.try_transfer_badge(&new_wallet, &old_wallet, &badge_id),
+        Err(Ok(Error::Soulbound))
+    );
+    assert_eq!(client.try_recover_badge(&99, &new_wallet), Err(Ok(Error::BadgeNotFound)));
+}
+
+#[test]
+fn test_revoke_badge() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let quest_platform = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let user = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, BadgeNFT);
+    let client = BadgeNFTClient::new(&env, &contract_id);
+    client.initialize(&admin, &quest_platform);
+
+    let class_id = symbol_short!("explorer");
+    create_test_class(&env, &client, &issuer, &class_id);
+    let first = client.mint_badge(&quest_platform, &user, &class_id, &None);
+    let second = client.mint_badge(&quest_platform, &user, &class_id, &None);
+
+    assert_eq!(
+        client.try_revoke_badge(&stranger, &first, &1),
+        Err(Ok(Error::NotIssuer))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    client.revoke_badge(&issuer, &first, &7);
+    assert_eq!(env.auths()[0].0, issuer);
+
+    // History is kept, but the badge no longer counts as owned
+    let badge = client.get_badge(&first).unwrap();
+    assert!(badge.revoked);
+    assert_eq!(
+        client.get_revocation(&first),
+        Some(Revocation {
+            reason: 7,
+            revoked_by: issuer.clone(),
+            revoked_at: 500,
+        })
+    );
+    assert_eq!(badge.owner, user);
+    assert_eq!(client.owner_of(&first), None);
+    assert_eq!(client.badges_of(&user, &0, &50), Vec::from_array(&env, [second]));
     assert_eq!(client.total_badges(), 1);
+    assert_eq!(client.class_supply(&class_id), 1);
+    assert_eq!(
+        client.class_holders(&class_id, &0, &10),
+        Vec::from_array(&env, [user.clone()])
+    );
+
+    assert_eq!(
+        client.try_transfer_badge(&user, &stranger, &first),
+        Err(Ok(Error::BadgeRevoked))
+    );
+    assert_eq!(
+        client.try_revoke_badge(&admin, &first, &7),
+        Err(Ok(Error::BadgeRevoked))
+    );
+
+    // The admin can revoke any class
+    client.revoke_badge(&admin, &second, &2);
+    assert_eq!(client.badges_of(&user, &0, &50).len(), 0);
+    assert_eq!(client.total_badges(), 0);
+
+    // New mints keep counting serials from where the class left off
+    let third = client.mint_badge(&quest_platform, &user, &class_id, &None);
+    assert_eq!(client.get_badge(&third).unwrap().serial, 3);
+}
+
+#[test]
+fn test_burn_badge() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let quest_platform = Address::generate(&env);
+    let user = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, BadgeNFT);
+    let client = BadgeNFTClient::new(&env, &contract_id);
+    client.initialize(&admin, &quest_platform);
+
+    let class_id = symbol_short!("explorer");
+    client.create_class(
+        &admin,
+        &class_id,
+        &badge_metadata(&env, "Explorer"),
+        &Some(1),
+        &true,
+        &None,
+    );
+    let badge_id = client.mint_badge(&quest_platform, &user, &class_id, &None);
+
+    assert_eq!(
+        client.try_burn_badge(&stranger, &badge_id),
+        Err(Ok(Error::NotOwner))
+    );
+
+    // Soulbound badges can still be burned by their owner
+    client.burn_badge(&user, &badge_id);
+    assert_eq!(env.auths()[0].0, user);
+    assert_eq!(client.get_badge(&badge_id), None);
+    assert_eq!(client.owner_of(&badge_id), None);
+    assert_eq!(client.badges_of(&user, &0, &50).len(), 0);
+    assert_eq!(client.total_badges(), 0);
+    assert_eq!(client.class_supply(&class_id), 0);
+    assert_eq!(client.class_holders(&class_id, &0, &10).len(), 0);
+
+    // Burning does not free up capped supply
+    assert_eq!(
+        client.try_mint_badge(&quest_platform, &user, &class_id, &None),
+        Err(Ok(Error::MaxSupplyReached))
+    );
+    assert_eq!(
+        client.try_burn_badge(&user, &badge_id),
+        Err(Ok(Error::BadgeNotFound))
+    );
+}
+
+#[test]
+fn test_expiring_badges() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let quest_platform = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, BadgeNFT);
+    let client = BadgeNFTClient::new(&env, &contract_id);
+    client.initialize(&admin, &quest_platform);
+
+    // Seasonal badges lapse 1000 seconds after minting
+    let seasonal = symbol_short!("season");
+    client.create_class(
+        &issuer,
+        &seasonal,
+        &badge_metadata(&env, "Active Explorer"),
+        &None,
+        &true,
+        &Some(1000),
+    );
+    let permanent = symbol_short!("explorer");
+    create_test_class(&env, &client, &issuer, &permanent);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    let lapsing = client.mint_badge(&quest_platform, &user, &seasonal, &None);
+    let lasting = client.mint_badge(&quest_platform, &user, &permanent, &None);
+    assert_eq!(client.get_badge(&lapsing).unwrap().valid_until, Some(1100));
+    assert_eq!(client.get_badge(&lasting).unwrap().valid_until, None);
+
+    env.ledger().with_mut(|li| li.timestamp = 1100);
+    assert!(client.is_valid(&lapsing));
+    assert_eq!(
+        client.get_user_valid_badges(&user, &0, &50),
+        Vec::from_array(&env, [lapsing, lasting])
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 1101);
+    assert!(!client.is_valid(&lapsing));
+    assert!(client.is_valid(&lasting));
+    assert_eq!(client.get_user_valid_badges(&user, &0, &50), Vec::from_array(&env, [lasting]));
+    // Lapsed badges are still owned
+    assert_eq!(client.badges_of(&user, &0, &50).len(), 2);
+
+    assert_eq!(
+        client.try_renew_badge(&admin, &lapsing, &5000),
+        Err(Ok(Error::NotIssuer))
+    );
+    assert_eq!(
+        client.try_renew_badge(&issuer, &lapsing, &1101),
+        Err(Ok(Error::InvalidExpiry))
+    );
+    client.renew_badge(&issuer, &lapsing, &5000);
+    assert_eq!(env.auths()[0].0, issuer);
+    assert!(client.is_valid(&lapsing));
+    assert_eq!(client.get_badge(&lapsing).unwrap().valid_until, Some(5000));
+
+    client.revoke_badge(&issuer, &lasting, &1);
+    assert!(!client.is_valid(&lasting));
+    assert!(!client.is_valid(&99));
+    assert_eq!(
+        client.try_renew_badge(&issuer, &lasting, &5000),
+        Err(Ok(Error::BadgeRevoked))
+    );
+}
+
+#[test]
+fn test_approve_and_transfer_from() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let quest_platform = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let marketplace = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, BadgeNFT);
+    let client = BadgeNFTClient::new(&env, &contract_id);
+    client.initialize(&admin, &quest_platform);
+
+    let class_id = symbol_short!("explorer");
+    create_test_class(&env, &client, &admin, &class_id);
+    let badge_id = client.mint_badge(&quest_platform, &owner, &class_id, &None);
+    let other_id = client.mint_badge(&quest_platform, &owner, &class_id, &None);
+
+    assert_eq!(
+        client.try_transfer_from(&marketplace, &owner, &buyer, &badge_id),
+        Err(Ok(Error::NotApproved))
+    );
+    assert_eq!(
+        client.try_approve(&marketplace, &buyer, &badge_id, &100),
+        Err(Ok(Error::NotOwner))
+    );
+
+    client.approve(&owner, &marketplace, &badge_id, &100);
+    assert_eq!(env.auths()[0].0, owner);
+    assert_eq!(client.get_approved(&badge_id), Some(marketplace.clone()));
+    assert_eq!(client.get_approved(&other_id), None);
+
+    // The approval only covers the approved badge
+    assert_eq!(
+        client.try_transfer_from(&marketplace, &owner, &buyer, &other_id),
+        Err(Ok(Error::NotApproved))
+    );
+
+    client.transfer_from(&marketplace, &owner, &buyer, &badge_id);
+    assert_eq!(env.auths()[0].0, marketplace);
+    assert_eq!(client.owner_of(&badge_id), Some(buyer.clone()));
+    assert_eq!(client.badges_of(&buyer, &0, &50), Vec::from_array(&env, [badge_id]));
+    assert_eq!(client.badges_of(&owner, &0, &50), Vec::from_array(&env, [other_id]));
+
+    // Approvals do not survive a change of owner
+    assert_eq!(client.get_approved(&badge_id), None);
+    assert_eq!(
+        client.try_transfer_from(&marketplace, &buyer, &owner, &badge_id),
+        Err(Ok(Error::NotApproved))
+    );
+}
+
+#[test]
+fn test_operator_approval_for_all() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let quest_platform = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let game_server = Address::generate(&env);
+    let friend = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, BadgeNFT);
+    let client = BadgeNFTClient::new(&env, &contract_id);
+    client.initialize(&admin, &quest_platform);
+
+    let class_id = symbol_short!("explorer");
+    create_test_class(&env, &client, &admin, &class_id);
+    let first = client.mint_badge(&quest_platform, &owner, &class_id, &None);
+    let second = client.mint_badge(&quest_platform, &owner, &class_id, &None);
+
+    assert!(!client.is_approved_for_all(&owner, &game_server));
+    client.set_approval_for_all(&owner, &game_server, &true, &100);
+    assert_eq!(env.auths()[0].0, owner);
+    assert!(client.is_approved_for_all(&owner, &game_server));
+
+    client.transfer_from(&game_server, &owner, &friend, &first);
+    client.transfer_from(&game_server, &owner, &friend, &second);
+    assert_eq!(client.badges_of(&friend, &0, &50).len(), 2);
+
+    // Operators act for one owner only
+    assert_eq!(
+        client.try_transfer_from(&game_server, &friend, &owner, &first),
+        Err(Ok(Error::NotApproved))
+    );
+
+    client.set_approval_for_all(&owner, &game_server, &false, &0);
+    assert!(!client.is_approved_for_all(&owner, &game_server));
+}
+
+#[test]
+fn test_approvals_expire_by_ledger() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let quest_platform = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let operator = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, BadgeNFT);
+    let client = BadgeNFTClient::new(&env, &contract_id);
+    client.initialize(&admin, &quest_platform);
+
+    let class_id = symbol_short!("explorer");
+    create_test_class(&env, &client, &admin, &class_id);
+    let badge_id = client.mint_badge(&quest_platform, &owner, &class_id, &None);
+
+    env.ledger().with_mut(|li| li.sequence_number = 100);
+    assert_eq!(
+        client.try_approve(&owner, &spender, &badge_id, &99),
+        Err(Ok(Error::ExpirationInPast))
+    );
+    assert_eq!(
+        client.try_set_approval_for_all(&owner, &operator, &true, &99),
+        Err(Ok(Error::ExpirationInPast))
+    );
+
+    client.approve(&owner, &spender, &badge_id, &110);
+    client.set_approval_for_all(&owner, &operator, &true, &110);
+
+    env.ledger().with_mut(|li| li.sequence_number = 110);
+    assert_eq!(client.get_approved(&badge_id), Some(spender.clone()));
+    assert!(client.is_approved_for_all(&owner, &operator));
+
+    env.ledger().with_mut(|li| li.sequence_number = 111);
+    assert_eq!(client.get_approved(&badge_id), None);
+    assert!(!client.is_approved_for_all(&owner, &operator));
+    assert_eq!(
+        client.try_transfer_from(&spender, &owner, &spender, &badge_id),
+        Err(Ok(Error::NotApproved))
+    );
+    assert_eq!(
+        client.try_transfer_from(&operator, &owner, &operator, &badge_id),
+        Err(Ok(Error::NotApproved))
+    );
+}
+
+#[test]
+fn test_soulbound_badges_cannot_be_approved() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let quest_platform = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, BadgeNFT);
+    let client = BadgeNFTClient::new(&env, &contract_id);
+    client.initialize(&admin, &quest_platform);
+
+    let class_id = symbol_short!("proof");
+    client.create_class(
+        &admin,
+        &class_id,
+        &badge_metadata(&env, "Proof of Completion"),
+        &None,
+        &true,
+        &None,
+    );
+    let badge_id = client.mint_badge(&quest_platform, &owner, &class_id, &None);
+
+    assert_eq!(
+        client.try_approve(&owner, &operator, &badge_id, &100),
+        Err(Ok(Error::Soulbound))
+    );
+    client.set_approval_for_all(&owner, &operator, &true, &100);
+    assert_eq!(
+        client.try_transfer_from(&operator, &owner, &operator, &badge_id),
+        Err(Ok(Error::Soulbound))
+    );
+}
+
+#[test]
+fn test_class_metadata_and_token_uri() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let quest_platform = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, BadgeNFT);
+    let client = BadgeNFTClient::new(&env, &contract_id);
+    client.initialize(&admin, &quest_platform);
+
+    let class_id = symbol_short!("dragon");
+    let mut metadata = BadgeMetadata {
+        name: String::from_str(&env, "Dragon Slayer"),
+        description: String::from_str(&env, "Defeated the dragon"),
+        image_uri: String::from_str(&env, "ipfs://dragon.png"),
+        rarity: Rarity::Legendary,
+        attributes: Map::from_array(
+            &env,
+            [(String::from_str(&env, "element"), String::from_str(&env, "fire"))],
+        ),
+    };
+    client.create_class(&issuer, &class_id, &metadata, &None, &false, &None);
+    assert_eq!(client.get_class(&class_id).unwrap().metadata, metadata);
+
+    let badge_id = client.mint_badge(&quest_platform, &user, &class_id, &None);
+    assert_eq!(client.try_token_uri(&badge_id), Err(Ok(Error::BaseUriNotSet)));
+
+    client.set_base_uri(&String::from_str(&env, "https://badges.example/meta/"));
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(
+        client.token_uri(&badge_id),
+        String::from_str(&env, "https://badges.example/meta/1")
+    );
+    assert_eq!(client.try_token_uri(&99), Err(Ok(Error::BadgeNotFound)));
+    assert_eq!(
+        client.try_set_base_uri(&String::from_str(&env, &"x".repeat(201))),
+        Err(Ok(Error::BaseUriTooLong))
+    );
+
+    // The issuer can update metadata until the admin freezes it
+    metadata.rarity = Rarity::Epic;
+    client.update_class_metadata(&issuer, &class_id, &metadata);
+    assert_eq!(client.get_class(&class_id).unwrap().metadata.rarity, Rarity::Epic);
+    assert_eq!(
+        client.try_update_class_metadata(&user, &class_id, &metadata),
+        Err(Ok(Error::NotIssuer))
+    );
+
+    client.freeze_class_metadata(&class_id);
+    assert_eq!(env.auths()[0].0, admin);
+    assert!(client.get_class(&class_id).unwrap().metadata_frozen);
+    assert_eq!(
+        client.try_update_class_metadata(&issuer, &class_id, &metadata),
+        Err(Ok(Error::MetadataFrozen))
+    );
+    assert_eq!(
+        client.try_update_class_metadata(&admin, &class_id, &metadata),
+        Err(Ok(Error::MetadataFrozen))
+    );
+    assert_eq!(client.try_freeze_class_metadata(&class_id), Err(Ok(Error::MetadataFrozen)));
+}
+
+#[test]
+fn test_forge_badge() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let quest_platform = Address::generate(&env);
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, BadgeNFT);
+    let client = BadgeNFTClient::new(&env, &contract_id);
+    client.initialize(&admin, &quest_platform);
+
+    let bronze = symbol_short!("bronze");
+    let silver = symbol_short!("silver");
+    create_test_class(&env, &client, &admin, &bronze);
+    create_test_class(&env, &client, &admin, &silver);
+
+    let recipe_id = symbol_short!("upgrade");
+    client.create_recipe(&recipe_id, &Map::from_array(&env, [(bronze.clone(), 3)]), &silver);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(client.get_recipes(), Vec::from_array(&env, [recipe_id.clone()]));
+    assert!(client.get_recipe(&recipe_id).unwrap().enabled);
+
+    let b1 = client.mint_badge(&quest_platform, &user, &bronze, &None);
+    let b2 = client.mint_badge(&quest_platform, &user, &bronze, &None);
+    let b3 = client.mint_badge(&quest_platform, &user, &bronze, &None);
+    let b4 = client.mint_badge(&quest_platform, &other, &bronze, &None);
+
+    // Inputs must match the recipe exactly and belong to the caller
+    assert_eq!(
+        client.try_forge_badge(&user, &recipe_id, &Vec::from_array(&env, [b1, b2])),
+        Err(Ok(Error::RecipeMismatch))
+    );
+    assert_eq!(
+        client.try_forge_badge(&user, &recipe_id, &Vec::from_array(&env, [b1, b2, b2])),
+        Err(Ok(Error::RecipeMismatch))
+    );
+    assert_eq!(
+        client.try_forge_badge(&user, &recipe_id, &Vec::from_array(&env, [b1, b2, b4])),
+        Err(Ok(Error::NotOwner))
+    );
+
+    let consumed = Vec::from_array(&env, [b1, b2, b3]);
+    let forged = client.forge_badge(&user, &recipe_id, &consumed);
+    assert_eq!(env.auths()[0].0, user);
+
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("bdg_forge"), user.clone()).into_val(&env),
+                (recipe_id.clone(), consumed, forged).into_val(&env),
+            ),
+        ]
+    );
+
+    assert_eq!(client.get_badge(&forged).unwrap().class_id, silver);
+    assert_eq!(client.badges_of(&user, &0, &50), Vec::from_array(&env, [forged]));
+    assert_eq!(client.get_badge(&b1), None);
+    assert_eq!(client.class_supply(&bronze), 1);
+    assert_eq!(client.class_supply(&silver), 1);
+    assert_eq!(client.total_badges(), 2);
+
+    client.set_recipe_enabled(&recipe_id, &false);
+    assert!(!client.get_recipe(&recipe_id).unwrap().enabled);
+    assert_eq!(
+        client.try_forge_badge(&other, &recipe_id, &Vec::from_array(&env, [b4])),
+        Err(Ok(Error::RecipeDisabled))
+    );
+    assert_eq!(
+        client.try_forge_badge(&user, &symbol_short!("missing"), &Vec::new(&env)),
+        Err(Ok(Error::RecipeNotFound))
+    );
+    assert_eq!(
+        client.try_create_recipe(&recipe_id, &Map::from_array(&env, [(bronze.clone(), 3)]), &silver),
+        Err(Ok(Error::RecipeAlreadyExists))
+    );
+    assert_eq!(
+        client.try_create_recipe(&symbol_short!("free"), &Map::from_array(&env, [(bronze, 0)]), &silver),
+        Err(Ok(Error::InvalidRecipe))
+    );
 }
 
+#[test]
+fn test_meta_achievements() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let quest_platform = Address::generate(&env);
+    let user = Address::generate(&env);
+    let friend = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, BadgeNFT);
+    let client = BadgeNFTClient::new(&env, &contract_id);
+    client.initialize(&admin, &quest_platform);
+
+    let forest = symbol_short!("forest");
+    let desert = symbol_short!("desert");
+    let veteran = symbol_short!("veteran");
+    let nomad = symbol_short!("nomad");
+    for class_id in [&forest, &desert, &veteran, &nomad] {
+        create_test_class(&env, &client, &admin, class_id);
+    }
+
+    let quests_rule = symbol_short!("quests2");
+    client.create_rule(&quests_rule, &RuleCondition::DistinctQuests(2), &veteran);
+    assert_eq!(env.auths()[0].0, admin);
+
+    // Two badges from the same quest don't count twice
+    let q1 = Some(symbol_short!("q1"));
+    client.mint_badge(&quest_platform, &user, &forest, &q1);
+    client.mint_badge(&quest_platform, &user, &forest, &q1);
+    assert!(!client.has_achievement(&user, &quests_rule));
+
+    // Other minters can't pass badges off as quest rewards
+    assert_eq!(
+        client.try_mint_badge(&admin, &user, &desert, &Some(symbol_short!("q9"))),
+        Err(Ok(Error::QuestBadgeRestricted))
+    );
+    client.mint_badge(&admin, &user, &desert, &None);
+    assert!(!client.has_achievement(&user, &quests_rule));
+
+    client.mint_badge(&quest_platform, &user, &desert, &Some(symbol_short!("q2")));
+    assert!(client.has_achievement(&user, &quests_rule));
+    assert_eq!(client.class_supply(&veteran), 1);
+
+    // Awarded only once
+    client.mint_badge(&quest_platform, &user, &desert, &Some(symbol_short!("q3")));
+    assert_eq!(client.class_supply(&veteran), 1);
+
+    // A transfer that completes a class set triggers the rule for the receiver
+    let desert_badge = client.mint_badge(&quest_platform, &user, &desert, &None);
+    let set_rule = symbol_short!("both");
+    client.create_rule(
+        &set_rule,
+        &RuleCondition::AllClasses(Vec::from_array(&env, [forest.clone(), desert.clone()])),
+        &nomad,
+    );
+    let forest_badge = client.mint_badge(&quest_platform, &friend, &forest, &None);
+    assert!(!client.has_achievement(&friend, &set_rule));
+    client.transfer_badge(&user, &friend, &desert_badge);
+    assert!(client.has_achievement(&friend, &set_rule));
+    assert_eq!(client.get_badge(&forest_badge).unwrap().owner, friend);
+
+    // Users who qualified before the rule existed are backfilled on demand
+    assert!(!client.has_achievement(&user, &set_rule));
+    let awarded = client.check_achievements(&user);
+    assert_eq!(awarded.len(), 1);
+    assert_eq!(client.get_badge(&awarded.get(0).unwrap()).unwrap().class_id, nomad);
+    assert_eq!(client.check_achievements(&user).len(), 0);
+
+    // Badges that leave a user's hands stop counting towards their rules
+    let trader = Address::generate(&env);
+    let traded = client.mint_badge(&quest_platform, &trader, &forest, &q1);
+    client.transfer_badge(&trader, &friend, &traded);
+    client.mint_badge(&quest_platform, &trader, &desert, &Some(symbol_short!("q2")));
+    assert!(!client.has_achievement(&trader, &quests_rule));
+    assert!(!client.has_achievement(&trader, &set_rule));
+
+    let burned = client.mint_badge(&quest_platform, &trader, &forest, &None);
+    client.burn_badge(&trader, &burned);
+    assert_eq!(client.check_achievements(&trader).len(), 0);
+    client.mint_badge(&quest_platform, &trader, &forest, &q1);
+    assert!(client.has_achievement(&trader, &quests_rule));
+    assert!(client.has_achievement(&trader, &set_rule));
+}
+
+#[test]
+fn test_rule_management() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let quest_platform = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, BadgeNFT);
+    let client = BadgeNFTClient::new(&env, &contract_id);
+    client.initialize(&admin, &quest_platform);
+
+    let reward = symbol_short!("veteran");
+    create_test_class(&env, &client, &admin, &reward);
+
+    let rule_id = symbol_short!("quests5");
+    client.create_rule(&rule_id, &RuleCondition::DistinctQuests(5), &reward);
+    assert_eq!(client.get_rules(), Vec::from_array(&env, [rule_id.clone()]));
+    assert_eq!(
+        client.try_create_rule(&rule_id, &RuleCondition::DistinctQuests(5), &reward),
+        Err(Ok(Error::RuleAlreadyExists))
+    );
+
+    client.update_rule(&rule_id, &RuleCondition::DistinctQuests(3), &reward);
+    assert_eq!(
+        client.get_rule(&rule_id).unwrap().condition,
+        RuleCondition::DistinctQuests(3)
+    );
+    assert_eq!(
+        client.try_update_rule(&rule_id, &RuleCondition::AllClasses(Vec::new(&env)), &reward),
+        Err(Ok(Error::InvalidRule))
+    );
+    assert_eq!(
+        client.try_update_rule(&rule_id, &RuleCondition::DistinctQuests(3), &symbol_short!("missing")),
+        Err(Ok(Error::ClassNotFound))
+    );
+
+    client.delete_rule(&rule_id);
+    assert_eq!(client.get_rule(&rule_id), None);
+    assert_eq!(client.get_rules().len(), 0);
+    assert_eq!(client.try_delete_rule(&rule_id), Err(Ok(Error::RuleNotFound)));
+    assert_eq!(
+        client.try_update_rule(&rule_id, &RuleCondition::DistinctQuests(3), &reward),
+        Err(Ok(Error::RuleNotFound))
+    );
+}
+
+#[test]
+fn test_badge_rental() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let quest_platform = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let friend = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, BadgeNFT);
+    let client = BadgeNFTClient::new(&env, &contract_id);
+    client.initialize(&admin, &quest_platform);
+
+    let class_id = symbol_short!("explorer");
+    create_test_class(&env, &client, &admin, &class_id);
+    let badge_id = client.mint_badge(&quest_platform, &owner, &class_id, &None);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    assert_eq!(
+        client.try_set_user(&friend, &badge_id, &friend, &2000),
+        Err(Ok(Error::NotApproved))
+    );
+    assert_eq!(
+        client.try_set_user(&owner, &badge_id, &friend, &1000),
+        Err(Ok(Error::InvalidExpiry))
+    );
+
+    client.set_user(&owner, &badge_id, &friend, &2000);
+    assert_eq!(env.auths()[0].0, owner);
+    assert_eq!(client.user_of(&badge_id), Some(friend.clone()));
+    assert_eq!(client.user_expires(&badge_id), Some(2000));
+    // Lending does not change ownership
+    assert_eq!(client.owner_of(&badge_id), Some(owner.clone()));
+
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+    assert_eq!(client.user_of(&badge_id), Some(friend.clone()));
+    env.ledger().with_mut(|li| li.timestamp = 2001);
+    assert_eq!(client.user_of(&badge_id), None);
+    assert_eq!(client.user_expires(&badge_id), None);
+
+    // Operators can lend on the owner's behalf, and the owner can end it early
+    client.set_approval_for_all(&owner, &operator, &true, &100);
+    client.set_user(&operator, &badge_id, &friend, &3000);
+    assert_eq!(client.user_of(&badge_id), Some(friend.clone()));
+    client.clear_user(&owner, &badge_id);
+    assert_eq!(client.user_of(&badge_id), None);
+
+    // A transfer ends the rental
+    client.set_user(&owner, &badge_id, &friend, &3000);
+    client.transfer_badge(&owner, &buyer, &badge_id);
+    assert_eq!(client.user_of(&badge_id), None);
+}
+
+#[test]
+fn test_enumerable_badge_queries() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let quest_platform = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let friend = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, BadgeNFT);
+    let client = BadgeNFTClient::new(&env, &contract_id);
+    client.initialize(&admin, &quest_platform);
+
+    let class_id = symbol_short!("explorer");
+    create_test_class(&env, &client, &admin, &class_id);
+
+    // The test budget is shared by every call below
+    env.budget().reset_unlimited();
+
+    // Every other badge comes from quest q1
+    let q1 = symbol_short!("q1");
+    let mut minted = Vec::new(&env);
+    for i in 0..60u32 {
+        let quest_id = if i % 2 == 0 { Some(q1.clone()) } else { None };
+        minted.push_back(client.mint_badge(&quest_platform, &owner, &class_id, &quest_id));
+    }
+
+    assert_eq!(client.balance_of(&owner), 60);
+    assert_eq!(client.badges_of(&owner, &0, &100).len(), 50);
+    assert_eq!(client.badges_of(&owner, &50, &50), minted.slice(50..));
+    assert_eq!(client.badges_of(&owner, &60, &10).len(), 0);
+    assert_eq!(client.badge_by_index(&0), minted.get(0));
+    assert_eq!(client.badge_by_index(&59), minted.get(59));
+    assert_eq!(client.badge_by_index(&60), None);
+
+    let quest_badges = client.badges_for_quest(&q1, &0, &50);
+    assert_eq!(quest_badges.len(), 30);
+    assert_eq!(quest_badges.get(1), minted.get(2));
+
+    // Transfers, burns and revocations keep every index dense
+    let first = minted.get(0).unwrap();
+    let second = minted.get(1).unwrap();
+    let third = minted.get(2).unwrap();
+    client.transfer_badge(&owner, &friend, &first);
+    client.burn_badge(&owner, &second);
+    client.revoke_badge(&admin, &third, &1);
+
+    assert_eq!(client.balance_of(&owner), 57);
+    assert_eq!(client.balance_of(&friend), 1);
+    assert_eq!(client.badges_of(&friend, &0, &10), Vec::from_array(&env, [first]));
+    assert_eq!(client.total_badges(), 58);
+    assert_eq!(client.badge_by_index(&58), None);
+    assert_eq!(client.badges_for_quest(&q1, &0, &50).len(), 29);
+
+    let mut owned = Vec::new(&env);
+    owned.append(&client.badges_of(&owner, &0, &50));
+    owned.append(&client.badges_of(&owner, &50, &50));
+    assert_eq!(owned.len(), 57);
+    for badge_id in [first, second, third] {
+        assert!(!owned.contains(badge_id));
+    }
+}
@@ -3,11 +3,173 @@
 //! Fully on-chain quest creation, completion, and reward distribution
 
 #![no_std]
+#![allow(clippy::too_many_arguments)]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short,
-    Address, Env, Symbol, Map, Vec, String,
+    contract, contractclient, contracterror, contractimpl, contracttype, panic_with_error,
+    symbol_short, token, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol, Vec, String,
 };
 
+const DAY_IN_LEDGERS: u32 = 17280;
+const INSTANCE_BUMP_AMOUNT: u32 = 7 * DAY_IN_LEDGERS;
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+const PERSISTENT_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+/// Upper bound on the number of records returned by a paginated query
+const MAX_PAGE_SIZE: u32 = 50;
+
+/// Leaderboard size used until the admin configures one
+const DEFAULT_LEADERBOARD_SIZE: u32 = 10;
+/// Upper bound on the configurable leaderboard size
+const MAX_LEADERBOARD_SIZE: u32 = 100;
+
+/// Upper bound on the number of quests in a quest's prerequisite chain
+const MAX_CHAIN_LEN: u32 = 50;
+
+/// Domain tag signed by quest verifiers, so attestations cannot be reused for other messages
+const ATTESTATION_DOMAIN: &str = "quest_platform:attestation:v1";
+
+/// Contract errors, codes are stable and surfaced to clients
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    NotInitialized = 1,
+    QuestAlreadyExists = 2,
+    QuestNotFound = 3,
+    QuestNotActive = 4,
+    QuestExpired = 5,
+    MaxCompletionsReached = 6,
+    AlreadyCompleted = 7,
+    NegativeRewardAmount = 8,
+    InvalidMaxCompletions = 9,
+    RewardOverflow = 10,
+    QuestAlreadyCancelled = 11,
+    InvalidLeaderboardSize = 12,
+    AlreadyInitialized = 13,
+    NoPendingAdmin = 14,
+    BadgeContractNotSet = 15,
+    BadgeClassNotOwned = 16,
+    ReviewRequired = 17,
+    NotQuestCreator = 18,
+    NotReviewer = 19,
+    SubmissionNotFound = 20,
+    SubmissionPending = 21,
+    SubmissionNotPending = 22,
+    VerifierNotSet = 23,
+    AttestationRequired = 24,
+    AttestationExpired = 25,
+    NonceAlreadyUsed = 26,
+    PrerequisiteNotFound = 27,
+    PrerequisiteNotMet = 28,
+    CyclicPrerequisite = 29,
+    ChainTooLong = 30,
+    NotEligible = 31,
+    ReviewNotRequired = 32,
+    StatusIndexCorrupt = 33,
+    QuestNotDraft = 34,
+    QuestAwardsNoBadge = 35,
+    QuestNotExpired = 36,
+}
+
+/// Subset of the BadgeNFT contract used to award quest badges
+#[contractclient(name = "BadgeNftClient")]
+pub trait BadgeNftInterface {
+    fn mint_badge(
+        env: Env,
+        minter: Address,
+        to: Address,
+        class_id: Symbol,
+        quest_id: Option<Symbol>,
+    ) -> u64;
+
+    fn class_issuer(env: Env, class_id: Symbol) -> Option<Address>;
+
+    fn user_of(env: Env, badge_id: u64) -> Option<Address>;
+
+    fn get_badge(env: Env, badge_id: u64) -> Option<Badge>;
+
+    fn is_valid(env: Env, badge_id: u64) -> bool;
+}
+
+/// Badge as returned by the BadgeNFT contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Badge {
+    pub id: u64,
+    pub class_id: Symbol,
+    pub serial: u32,
+    pub quest_id: Option<Symbol>,
+    pub owner: Address,
+    pub minted_at: u64,
+    pub revoked: bool,
+    pub valid_until: Option<u64>,
+}
+
+/// Who may complete a quest
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Eligibility {
+    /// Anyone
+    Open,
+    /// Holders of a valid badge of a BadgeNFT class, proven with the badge ID
+    BadgeClass(Symbol),
+    /// Holders of a valid badge awarded by another quest, proven with the badge ID
+    QuestBadge(Symbol),
+    /// Only addresses on the quest's list
+    Allowlist,
+    /// Anyone except addresses on the quest's list
+    Denylist,
+    /// Addresses in a sha256 Merkle tree with this root, proven on completion
+    MerkleRoot(BytesN<32>),
+}
+
+/// What a user presents to pass a quest's eligibility policy
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EligibilityProof {
+    /// Nothing, for open and list-based policies
+    None,
+    /// The user's Merkle proof on Merkle-gated quests
+    Merkle(Vec<BytesN<32>>),
+    /// A badge the user owns or currently rents on badge-gated quests
+    Badge(u64),
+}
+
+/// Persistent storage keys, one ledger entry per quest and per completion
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Quest(Symbol),
+    Completion(Address, Symbol),
+    /// Number of quests currently in a status
+    StatusCount(Symbol),
+    /// Quest ID stored at a position of a status index
+    StatusEntry(Symbol, u32),
+    /// Position of a quest inside the index of its current status
+    StatusPos(Symbol),
+    /// Aggregated completion stats of a user
+    UserStats(Address),
+    /// Quest ID of a user's n-th completion
+    UserCompletion(Address, u32),
+    /// Top-N users ranked by completions, then rewards earned
+    Leaderboard,
+    /// Address allowed to review submissions of a quest
+    Reviewer(Symbol, Address),
+    /// Latest completion submission of a user for a quest
+    Submission(Address, Symbol),
+    /// Number of users who submitted to a quest
+    SubmissionCount(Symbol),
+    /// User of a quest's n-th submission
+    SubmissionEntry(Symbol, u32),
+    /// Ed25519 public key whose attestations complete a quest
+    Verifier(Symbol),
+    /// Attestation nonce already consumed for a quest
+    UsedNonce(Symbol, u64),
+    /// Address on a quest's allowlist or denylist
+    Listed(Symbol, Address),
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Quest {
@@ -17,12 +179,24 @@ pub struct Quest {
     pub description: String,
     pub reward_amount: i128,
     pub reward_token: Address,
-    pub badge_id: Option<Symbol>, // NFT badge for completion
-    pub status: Symbol, // "active", "completed", "cancelled"
+    pub badge_id: Option<Symbol>, // BadgeNFT class awarded on completion
+    pub status: Symbol, // "draft", "active", "completed", "cancelled"
     pub created_at: u64,
     pub expires_at: Option<u64>,
     pub max_completions: Option<i128>,
     pub current_completions: i128,
+    pub review_required: bool, // Completions must go through submit_completion
+    pub prerequisites: Vec<Symbol>, // Quests a user must complete first
+    pub eligibility: Eligibility,
+}
+
+/// A quest in a prerequisite chain, with its distance from the queried quest
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QuestChainNode {
+    pub quest_id: Symbol,
+    pub prerequisites: Vec<Symbol>,
+    pub depth: u32,
 }
 
 #[contracttype]
@@ -31,7 +205,589 @@ pub struct QuestCompletion {
     pub user: Address,
     pub quest_id: Symbol,
     pub completed_at: u64,
+    pub reward_amount: i128,
     pub reward_claimed: bool,
+    pub badge_token: Option<u64>, // Badge minted for this completion
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Submission {
+    pub user: Address,
+    pub quest_id: Symbol,
+    pub proof_hash: BytesN<32>,
+    pub proof_uri: String,
+    pub status: Symbol, // "pending", "approved", "rejected"
+    pub submitted_at: u64,
+    pub reviewed_by: Option<Address>,
+    pub reviewed_at: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserStats {
+    pub total_completions: u32,
+    pub total_rewards: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LeaderboardEntry {
+    pub user: Address,
+    pub completions: u32,
+    pub rewards: i128,
+}
+
+fn bump_instance(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+fn bump_persistent(env: &Env, key: &DataKey) {
+    env.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+fn read_admin(env: &Env) -> Result<Address, Error> {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("admin"))
+        .ok_or(Error::NotInitialized)
+}
+
+fn read_badge_contract(env: &Env) -> Result<Address, Error> {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("badge_nft"))
+        .ok_or(Error::BadgeContractNotSet)
+}
+
+/// Mint a new instance of the quest's badge class to the user through the BadgeNFT contract
+fn award_badge(env: &Env, quest: &Quest, user: &Address) -> Result<Option<u64>, Error> {
+    let class_id = match &quest.badge_id {
+        Some(class_id) => class_id,
+        None => return Ok(None),
+    };
+    let badge_contract = read_badge_contract(env)?;
+
+    let badge_token = BadgeNftClient::new(env, &badge_contract).mint_badge(
+        &env.current_contract_address(),
+        user,
+        class_id,
+        &Some(quest.id.clone()),
+    );
+
+    Ok(Some(badge_token))
+}
+
+fn read_quest(env: &Env, quest_id: &Symbol) -> Option<Quest> {
+    let key = DataKey::Quest(quest_id.clone());
+    let quest = env.storage().persistent().get::<DataKey, Quest>(&key);
+    if quest.is_some() {
+        bump_persistent(env, &key);
+    }
+    quest
+}
+
+fn write_quest(env: &Env, quest: &Quest) {
+    let key = DataKey::Quest(quest.id.clone());
+    env.storage().persistent().set(&key, quest);
+    bump_persistent(env, &key);
+}
+
+fn read_completion(env: &Env, user: &Address, quest_id: &Symbol) -> Option<QuestCompletion> {
+    let key = DataKey::Completion(user.clone(), quest_id.clone());
+    let completion = env.storage().persistent().get::<DataKey, QuestCompletion>(&key);
+    if completion.is_some() {
+        bump_persistent(env, &key);
+    }
+    completion
+}
+
+fn write_completion(env: &Env, completion: &QuestCompletion) {
+    let key = DataKey::Completion(completion.user.clone(), completion.quest_id.clone());
+    env.storage().persistent().set(&key, completion);
+    bump_persistent(env, &key);
+}
+
+fn read_submission(env: &Env, user: &Address, quest_id: &Symbol) -> Option<Submission> {
+    let key = DataKey::Submission(user.clone(), quest_id.clone());
+    let submission = env.storage().persistent().get::<DataKey, Submission>(&key);
+    if submission.is_some() {
+        bump_persistent(env, &key);
+    }
+    submission
+}
+
+fn write_submission(env: &Env, submission: &Submission) {
+    let key = DataKey::Submission(submission.user.clone(), submission.quest_id.clone());
+    env.storage().persistent().set(&key, submission);
+    bump_persistent(env, &key);
+}
+
+fn read_submission_count(env: &Env, quest_id: &Symbol) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SubmissionCount(quest_id.clone()))
+        .unwrap_or(0)
+}
+
+/// Append a user to the quest's submission index
+fn add_submission_entry(env: &Env, quest_id: &Symbol, user: &Address) {
+    let count = read_submission_count(env, quest_id);
+
+    let entry_key = DataKey::SubmissionEntry(quest_id.clone(), count);
+    env.storage().persistent().set(&entry_key, user);
+    bump_persistent(env, &entry_key);
+
+    let count_key = DataKey::SubmissionCount(quest_id.clone());
+    env.storage().persistent().set(&count_key, &(count + 1));
+    bump_persistent(env, &count_key);
+}
+
+fn can_review(env: &Env, quest: &Quest, reviewer: &Address) -> bool {
+    *reviewer == quest.creator
+        || env
+            .storage()
+            .persistent()
+            .has(&DataKey::Reviewer(quest.id.clone(), reviewer.clone()))
+}
+
+/// Load a quest and check the caller is its creator
+fn read_own_quest(env: &Env, creator: &Address, quest_id: &Symbol) -> Result<Quest, Error> {
+    let quest = read_quest(env, quest_id).ok_or(Error::QuestNotFound)?;
+    if quest.creator != *creator {
+        return Err(Error::NotQuestCreator);
+    }
+    Ok(quest)
+}
+
+/// Load a pending submission and check the caller may review it
+fn read_pending_submission(
+    env: &Env,
+    reviewer: &Address,
+    user: &Address,
+    quest_id: &Symbol,
+) -> Result<(Quest, Submission), Error> {
+    let quest = read_quest(env, quest_id).ok_or(Error::QuestNotFound)?;
+    if !can_review(env, &quest, reviewer) {
+        return Err(Error::NotReviewer);
+    }
+    let submission = read_submission(env, user, quest_id).ok_or(Error::SubmissionNotFound)?;
+    if submission.status != symbol_short!("pending") {
+        return Err(Error::SubmissionNotPending);
+    }
+    if read_completion(env, user, quest_id).is_some() {
+        return Err(Error::AlreadyCompleted);
+    }
+    Ok((quest, submission))
+}
+
+fn read_verifier(env: &Env, quest_id: &Symbol) -> Option<BytesN<32>> {
+    let key = DataKey::Verifier(quest_id.clone());
+    let verifier = env.storage().persistent().get::<DataKey, BytesN<32>>(&key);
+    if verifier.is_some() {
+        bump_persistent(env, &key);
+    }
+    verifier
+}
+
+/// Message a verifier signs to attest that a user completed a quest
+/// XDR of (domain, platform contract, quest ID, user, nonce, expiry)
+fn attestation_payload(env: &Env, user: &Address, quest_id: &Symbol, nonce: u64, expiry: u64) -> Bytes {
+    (
+        String::from_str(env, ATTESTATION_DOMAIN),
+        env.current_contract_address(),
+        quest_id.clone(),
+        user.clone(),
+        nonce,
+        expiry,
+    )
+        .to_xdr(env)
+}
+
+/// Walk a quest's prerequisites breadth-first, each quest is listed once at its shallowest depth
+/// Fails with ChainTooLong instead of walking more than MAX_CHAIN_LEN quests
+fn read_quest_chain(env: &Env, quest_id: &Symbol, prerequisites: &Vec<Symbol>) -> Result<Vec<QuestChainNode>, Error> {
+    let mut chain = Vec::new(env);
+    chain.push_back(QuestChainNode {
+        quest_id: quest_id.clone(),
+        prerequisites: prerequisites.clone(),
+        depth: 0,
+    });
+
+    let mut next = 0;
+    while next < chain.len() {
+        let node = chain.get(next).unwrap();
+        next += 1;
+        for prerequisite in node.prerequisites.iter() {
+            if chain.iter().any(|seen| seen.quest_id == prerequisite) {
+                continue;
+            }
+            if chain.len() >= MAX_CHAIN_LEN {
+                return Err(Error::ChainTooLong);
+            }
+            let quest = read_quest(env, &prerequisite).ok_or(Error::PrerequisiteNotFound)?;
+            chain.push_back(QuestChainNode {
+                quest_id: prerequisite,
+                prerequisites: quest.prerequisites,
+                depth: node.depth + 1,
+            });
+        }
+    }
+    Ok(chain)
+}
+
+/// Check a badge is valid, matches a badge-gated eligibility policy and is held by the user
+/// A rented badge counts for its renter and not its owner until the rental ends
+fn holds_badge(env: &Env, user: &Address, eligibility: &Eligibility, badge_id: u64) -> Result<bool, Error> {
+    // A quest badge must also be of the class that quest awards
+    let (class_id, quest_id) = match eligibility {
+        Eligibility::BadgeClass(class_id) => (class_id.clone(), None),
+        Eligibility::QuestBadge(quest_id) => {
+            match read_quest(env, quest_id).and_then(|quest| quest.badge_id) {
+                Some(class_id) => (class_id, Some(quest_id.clone())),
+                None => return Ok(false),
+            }
+        }
+        _ => return Ok(false),
+    };
+    let badges = BadgeNftClient::new(env, &read_badge_contract(env)?);
+
+    let Some(badge) = badges.get_badge(&badge_id) else {
+        return Ok(false);
+    };
+    if badge.class_id != class_id || (quest_id.is_some() && badge.quest_id != quest_id) {
+        return Ok(false);
+    }
+    let holder = badges.user_of(&badge_id).unwrap_or(badge.owner);
+    Ok(holder == *user && badges.is_valid(&badge_id))
+}
+
+/// Check a Merkle proof of a user's address against a root
+/// leaf = sha256(0x00 || XDR of the address), node = sha256(0x01 || lower child || higher child)
+fn in_merkle_tree(env: &Env, root: &BytesN<32>, user: &Address, proof: &Vec<BytesN<32>>) -> bool {
+    let mut leaf = Bytes::from_array(env, &[0]);
+    leaf.append(&user.clone().to_xdr(env));
+    let mut hash: BytesN<32> = env.crypto().sha256(&leaf).into();
+
+    for sibling in proof.iter() {
+        let (low, high) = if hash <= sibling { (hash, sibling) } else { (sibling, hash) };
+        let mut node = Bytes::from_array(env, &[1]);
+        node.append(&low.into());
+        node.append(&high.into());
+        hash = env.crypto().sha256(&node).into();
+    }
+    hash == *root
+}
+
+/// Check a user passes the quest's eligibility policy and has completed its prerequisites
+/// Merkle- and badge-gated quests need the matching proof, other policies ignore it
+fn check_eligible(
+    env: &Env,
+    quest: &Quest,
+    user: &Address,
+    proof: &EligibilityProof,
+) -> Result<(), Error> {
+    let listed = || {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Listed(quest.id.clone(), user.clone()))
+    };
+    let eligible = match &quest.eligibility {
+        Eligibility::Open => true,
+        Eligibility::Allowlist => listed(),
+        Eligibility::Denylist => !listed(),
+        Eligibility::MerkleRoot(root) => match proof {
+            EligibilityProof::Merkle(path) => in_merkle_tree(env, root, user, path),
+            _ => false,
+        },
+        badge_gate => match proof {
+            EligibilityProof::Badge(badge_id) => holds_badge(env, user, badge_gate, *badge_id)?,
+            _ => false,
+        },
+    };
+    if !eligible {
+        return Err(Error::NotEligible);
+    }
+
+    for prerequisite in quest.prerequisites.iter() {
+        if read_completion(env, user, &prerequisite).is_none() {
+            return Err(Error::PrerequisiteNotMet);
+        }
+    }
+    Ok(())
+}
+
+/// Check the quest still has a reward slot open
+fn check_open(quest: &Quest) -> Result<(), Error> {
+    if quest.status != symbol_short!("active") {
+        return Err(Error::QuestNotActive);
+    }
+    if let Some(max) = quest.max_completions {
+        if quest.current_completions >= max {
+            return Err(Error::MaxCompletionsReached);
+        }
+    }
+    Ok(())
+}
+
+/// Check a user may still complete the quest right now
+fn check_completable(
+    env: &Env,
+    quest: &Quest,
+    user: &Address,
+    proof: &EligibilityProof,
+) -> Result<(), Error> {
+    check_open(quest)?;
+
+    if let Some(expires_at) = quest.expires_at {
+        if env.ledger().timestamp() > expires_at {
+            return Err(Error::QuestExpired);
+        }
+    }
+
+    if read_completion(env, user, &quest.id).is_some() {
+        return Err(Error::AlreadyCompleted);
+    }
+
+    check_eligible(env, quest, user, proof)
+}
+
+/// Pay out a completion, mint its badge and record it
+fn finish_completion(env: &Env, mut quest: Quest, user: &Address) -> Result<(), Error> {
+    // Update quest completion count
+    quest.current_completions += 1;
+    if let Some(max) = quest.max_completions {
+        if quest.current_completions >= max {
+            set_status(env, &mut quest, symbol_short!("completed"));
+        }
+    }
+
+    // Pay the reward out of the escrow funded at quest creation
+    let reward_amount = quest.reward_amount;
+    if reward_amount > 0 {
+        token::Client::new(env, &quest.reward_token).transfer(
+            &env.current_contract_address(),
+            user,
+            &reward_amount,
+        );
+    }
+
+    let badge_token = award_badge(env, &quest, user)?;
+
+    // Record completion
+    let completion = QuestCompletion {
+        user: user.clone(),
+        quest_id: quest.id.clone(),
+        completed_at: env.ledger().timestamp(),
+        reward_amount,
+        reward_claimed: true,
+        badge_token,
+    };
+
+    write_completion(env, &completion);
+    let stats = record_user_completion(env, &completion);
+    update_leaderboard(env, user, &stats);
+    write_quest(env, &quest);
+
+    env.events().publish(
+        (symbol_short!("quest_dn"), user.clone()),
+        (quest.id, reward_amount),
+    );
+
+    Ok(())
+}
+
+fn read_user_stats(env: &Env, user: &Address) -> UserStats {
+    let key = DataKey::UserStats(user.clone());
+    match env.storage().persistent().get::<DataKey, UserStats>(&key) {
+        Some(stats) => {
+            bump_persistent(env, &key);
+            stats
+        }
+        None => UserStats {
+            total_completions: 0,
+            total_rewards: 0,
+        },
+    }
+}
+
+/// Append a completion to the user's history and update their totals
+fn record_user_completion(env: &Env, completion: &QuestCompletion) -> UserStats {
+    let mut stats = read_user_stats(env, &completion.user);
+
+    let entry_key = DataKey::UserCompletion(completion.user.clone(), stats.total_completions);
+    env.storage().persistent().set(&entry_key, &completion.quest_id);
+    bump_persistent(env, &entry_key);
+
+    stats.total_completions += 1;
+    stats.total_rewards += completion.reward_amount;
+    let stats_key = DataKey::UserStats(completion.user.clone());
+    env.storage().persistent().set(&stats_key, &stats);
+    bump_persistent(env, &stats_key);
+    stats
+}
+
+fn read_leaderboard(env: &Env) -> Vec<LeaderboardEntry> {
+    let key = DataKey::Leaderboard;
+    match env.storage().persistent().get::<DataKey, Vec<LeaderboardEntry>>(&key) {
+        Some(board) => {
+            bump_persistent(env, &key);
+            board
+        }
+        None => Vec::new(env),
+    }
+}
+
+fn write_leaderboard(env: &Env, board: &Vec<LeaderboardEntry>) {
+    let key = DataKey::Leaderboard;
+    env.storage().persistent().set(&key, board);
+    bump_persistent(env, &key);
+}
+
+fn read_leaderboard_size(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("lb_size"))
+        .unwrap_or(DEFAULT_LEADERBOARD_SIZE)
+}
+
+/// Re-rank a user after their stats changed
+/// Users tied with an existing entry are placed after it
+fn update_leaderboard(env: &Env, user: &Address, stats: &UserStats) {
+    let mut board = read_leaderboard(env);
+
+    if let Some(pos) = board.iter().position(|entry| entry.user == *user) {
+        board.remove(pos as u32);
+    }
+
+    let pos = board
+        .iter()
+        .position(|entry| {
+            stats.total_completions > entry.completions
+                || (stats.total_completions == entry.completions
+                    && stats.total_rewards > entry.rewards)
+        })
+        .map(|pos| pos as u32)
+        .unwrap_or(board.len());
+
+    let size = read_leaderboard_size(env);
+    if pos < size {
+        board.insert(
+            pos,
+            LeaderboardEntry {
+                user: user.clone(),
+                completions: stats.total_completions,
+                rewards: stats.total_rewards,
+            },
+        );
+        while board.len() > size {
+            board.pop_back();
+        }
+    }
+
+    write_leaderboard(env, &board);
+}
+
+fn read_status_count(env: &Env, status: &Symbol) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::StatusCount(status.clone()))
+        .unwrap_or(0)
+}
+
+fn write_status_count(env: &Env, status: &Symbol, count: u32) {
+    let key = DataKey::StatusCount(status.clone());
+    env.storage().persistent().set(&key, &count);
+    bump_persistent(env, &key);
+}
+
+fn write_status_entry(env: &Env, status: &Symbol, pos: u32, quest_id: &Symbol) {
+    let entry_key = DataKey::StatusEntry(status.clone(), pos);
+    env.storage().persistent().set(&entry_key, quest_id);
+    bump_persistent(env, &entry_key);
+
+    let pos_key = DataKey::StatusPos(quest_id.clone());
+    env.storage().persistent().set(&pos_key, &pos);
+    bump_persistent(env, &pos_key);
+}
+
+/// Append a quest to the end of a status index
+fn index_add(env: &Env, status: &Symbol, quest_id: &Symbol) {
+    let count = read_status_count(env, status);
+    write_status_entry(env, status, count, quest_id);
+    write_status_count(env, status, count + 1);
+}
+
+/// Remove a quest from a status index by moving the last entry into its slot
+fn index_remove(env: &Env, status: &Symbol, quest_id: &Symbol) {
+    let pos: u32 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::StatusPos(quest_id.clone()))
+        .unwrap_or_else(|| panic_with_error!(env, Error::StatusIndexCorrupt));
+    let last = read_status_count(env, status) - 1;
+
+    if pos != last {
+        let moved: Symbol = env
+            .storage()
+            .persistent()
+            .get(&DataKey::StatusEntry(status.clone(), last))
+            .unwrap_or_else(|| panic_with_error!(env, Error::StatusIndexCorrupt));
+        write_status_entry(env, status, pos, &moved);
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&DataKey::StatusEntry(status.clone(), last));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::StatusPos(quest_id.clone()));
+    write_status_count(env, status, last);
+}
+
+/// Move a quest to a new status, keeping the status indexes consistent
+fn set_status(env: &Env, quest: &mut Quest, status: Symbol) {
+    if quest.status == status {
+        return;
+    }
+    index_remove(env, &quest.status, &quest.id);
+    index_add(env, &status, &quest.id);
+    quest.status = status;
+}
+
+/// Return the unused part of a quest's escrow to its creator and cancel the quest
+fn refund_and_cancel(env: &Env, quest: &mut Quest) -> i128 {
+    let remaining_slots = quest.max_completions.unwrap_or(0) - quest.current_completions;
+    let refund = quest.reward_amount * remaining_slots;
+    if refund > 0 {
+        token::Client::new(env, &quest.reward_token).transfer(
+            &env.current_contract_address(),
+            &quest.creator,
+            &refund,
+        );
+    }
+
+    set_status(env, quest, symbol_short!("cancelled"));
+    write_quest(env, quest);
+    refund
+}
+
+fn read_quest_page(env: &Env, status: &Symbol, offset: u32, limit: u32) -> Vec<Quest> {
+    let mut quests = Vec::new(env);
+    let count = read_status_count(env, status);
+    let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+
+    for pos in offset..end {
+        let key = DataKey::StatusEntry(status.clone(), pos);
+        let quest_id: Symbol = env.storage().persistent().get(&key).unwrap();
+        bump_persistent(env, &key);
+        quests.push_back(read_quest(env, &quest_id).unwrap());
+    }
+    quests
 }
 
 #[contract]
@@ -40,14 +796,23 @@ pub struct QuestPlatform;
 #[contractimpl]
 impl QuestPlatform {
     /// Initialize the quest platform
-    pub fn initialize(env: Env, admin: Address, reward_token: Address) {
+    pub fn initialize(env: Env, admin: Address, reward_token: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&symbol_short!("init")) {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+
         env.storage().instance().set(&symbol_short!("admin"), &admin);
         env.storage().instance().set(&symbol_short!("reward_tk"), &reward_token);
         env.storage().instance().set(&symbol_short!("init"), &true);
         env.storage().instance().set(&symbol_short!("quest_cnt"), &0i128);
+        bump_instance(&env);
+
+        Ok(())
     }
 
-    /// Create a new quest
+    /// Create a new quest as a draft, see `activate_quest`
+    /// Prerequisites must already exist, so quest chains can never form a cycle
     pub fn create_quest(
         env: Env,
         creator: Address,
@@ -58,41 +823,70 @@ impl QuestPlatform {
         badge_id: Option<Symbol>,
         expires_at: Option<u64>,
         max_completions: Option<i128>,
-    ) -> bool {
+        prerequisites: Vec<Symbol>,
+    ) -> Result<bool, Error> {
         creator.require_auth();
 
+        if reward_amount < 0 {
+            return Err(Error::NegativeRewardAmount);
+        }
+
+        // Rewards are escrowed up front, so the number of payouts must be bounded
+        let escrow_amount = match max_completions {
+            Some(max) if max > 0 => reward_amount
+                .checked_mul(max)
+                .ok_or(Error::RewardOverflow)?,
+            _ => return Err(Error::InvalidMaxCompletions),
+        };
+
+        let reward_token: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("reward_tk"))
+            .ok_or(Error::NotInitialized)?;
+
+        // Creators may only award badge classes they issued
+        if let Some(class_id) = &badge_id {
+            let badge_contract = read_badge_contract(&env)?;
+            let issuer = BadgeNftClient::new(&env, &badge_contract).class_issuer(class_id);
+            if issuer != Some(creator.clone()) {
+                return Err(Error::BadgeClassNotOwned);
+            }
+        }
+
+        if prerequisites.contains(&quest_id) {
+            return Err(Error::CyclicPrerequisite);
+        }
+        read_quest_chain(&env, &quest_id, &prerequisites)?;
+
         let current_time = env.ledger().timestamp();
-        
+
         let quest = Quest {
             id: quest_id.clone(),
             creator: creator.clone(),
             title: title.clone(),
             description: description.clone(),
             reward_amount,
-            reward_token: env
-                .storage()
-                .instance()
-                .get(&symbol_short!("reward_tk"))
-                .expect("Contract not initialized - call initialize first"),
+            reward_token: reward_token.clone(),
             badge_id: badge_id.clone(),
-            status: symbol_short!("active"),
+            status: symbol_short!("draft"),
             created_at: current_time,
             expires_at,
             max_completions,
             current_completions: 0,
+            review_required: false,
+            prerequisites,
+            eligibility: Eligibility::Open,
         };
 
-        let mut quests: Map<Symbol, Quest> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("quests"))
-            .unwrap_or(Map::new(&env));
+        bump_instance(&env);
 
-        if quests.get(quest_id.clone()).is_some() {
-            panic!("Quest already exists");
+        if env.storage().persistent().has(&DataKey::Quest(quest_id.clone())) {
+            return Err(Error::QuestAlreadyExists);
         }
 
-        quests.set(quest_id.clone(), quest);
+        write_quest(&env, &quest);
+        index_add(&env, &quest.status, &quest_id);
 
         let mut count: i128 = env
             .storage()
@@ -102,190 +896,614 @@ impl QuestPlatform {
         count += 1;
         env.storage().instance().set(&symbol_short!("quest_cnt"), &count);
 
-        env.storage().instance().set(&symbol_short!("quests"), &quests);
+        // Pull the full reward pool from the creator into contract escrow
+        if escrow_amount > 0 {
+            token::Client::new(&env, &reward_token).transfer(
+                &creator,
+                &env.current_contract_address(),
+                &escrow_amount,
+            );
+        }
 
         env.events().publish(
             (symbol_short!("quest_crt"), creator),
             (quest_id, title, reward_amount),
         );
 
-        true
+        Ok(true)
+    }
+
+    /// Creator: Open a draft quest for completions
+    /// Set up review, verifiers and eligibility first, the escrow pays out as soon as the quest is live
+    pub fn activate_quest(env: Env, creator: Address, quest_id: Symbol) -> Result<(), Error> {
+        creator.require_auth();
+        bump_instance(&env);
+
+        let mut quest = read_own_quest(&env, &creator, &quest_id)?;
+        if quest.status != symbol_short!("draft") {
+            return Err(Error::QuestNotDraft);
+        }
+        set_status(&env, &mut quest, symbol_short!("active"));
+        write_quest(&env, &quest);
+
+        env.events().publish(
+            (symbol_short!("quest_act"), creator),
+            quest_id,
+        );
+
+        Ok(())
     }
 
     /// Complete a quest and claim rewards
+    /// `proof` is the user's Merkle proof or badge on gated quests and `None` otherwise
     pub fn complete_quest(
         env: Env,
         user: Address,
         quest_id: Symbol,
-    ) -> bool {
+        proof: EligibilityProof,
+    ) -> Result<bool, Error> {
         user.require_auth();
+        bump_instance(&env);
 
-        let mut quests: Map<Symbol, Quest> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("quests"))
-            .unwrap_or(Map::new(&env));
+        let quest = read_quest(&env, &quest_id).ok_or(Error::QuestNotFound)?;
+        if quest.review_required {
+            return Err(Error::ReviewRequired);
+        }
+        if read_verifier(&env, &quest_id).is_some() {
+            return Err(Error::AttestationRequired);
+        }
+        check_completable(&env, &quest, &user, &proof)?;
 
-        let mut quest = quests.get(quest_id.clone()).unwrap();
+        finish_completion(&env, quest, &user)?;
 
-        // Validate quest status
-        if quest.status != symbol_short!("active") {
-            panic!("Quest is not active");
-        }
+        Ok(true)
+    }
 
-        // Check expiration
-        if let Some(expires_at) = quest.expires_at {
-            let current_time = env.ledger().timestamp();
-            if current_time > expires_at {
-                quest.status = symbol_short!("cancelled");
-                quests.set(quest_id.clone(), quest);
-                env.storage().instance().set(&symbol_short!("quests"), &quests);
-                panic!("Quest has expired");
-            }
+    /// Complete a quest with an attestation signed by the quest's verifier
+    /// The signature covers the payload built by `attestation_payload`, an invalid one aborts the call
+    /// A valid attestation stands in for reviewer approval
+    /// `proof` is only needed for Merkle- or badge-gated quests, pass `None` otherwise
+    pub fn complete_quest_with_attestation(
+        env: Env,
+        user: Address,
+        quest_id: Symbol,
+        nonce: u64,
+        expiry: u64,
+        signature: BytesN<64>,
+        proof: EligibilityProof,
+    ) -> Result<bool, Error> {
+        user.require_auth();
+        bump_instance(&env);
+
+        let quest = read_quest(&env, &quest_id).ok_or(Error::QuestNotFound)?;
+        let verifier = read_verifier(&env, &quest_id).ok_or(Error::VerifierNotSet)?;
+
+        if env.ledger().timestamp() > expiry {
+            return Err(Error::AttestationExpired);
+        }
+        let nonce_key = DataKey::UsedNonce(quest_id.clone(), nonce);
+        if env.storage().persistent().has(&nonce_key) {
+            return Err(Error::NonceAlreadyUsed);
         }
+        check_completable(&env, &quest, &user, &proof)?;
 
-        // Check max completions
-        if let Some(max) = quest.max_completions {
-            if quest.current_completions >= max {
-                quest.status = symbol_short!("completed");
-                quests.set(quest_id.clone(), quest);
-                env.storage().instance().set(&symbol_short!("quests"), &quests);
-                panic!("Quest max completions reached");
-            }
+        let payload = attestation_payload(&env, &user, &quest_id, nonce, expiry);
+        env.crypto().ed25519_verify(&verifier, &payload, &signature);
+
+        env.storage().persistent().set(&nonce_key, &true);
+        bump_persistent(&env, &nonce_key);
+
+        finish_completion(&env, quest, &user)?;
+
+        env.events().publish(
+            (symbol_short!("attested"), user),
+            (quest_id, nonce),
+        );
+
+        Ok(true)
+    }
+
+    /// Creator: Set the ed25519 public key whose attestations complete a quest
+    /// Once set, the quest can no longer be completed without an attestation, so set it on the draft
+    pub fn set_verifier(
+        env: Env,
+        creator: Address,
+        quest_id: Symbol,
+        public_key: BytesN<32>,
+    ) -> Result<(), Error> {
+        creator.require_auth();
+        bump_instance(&env);
+
+        read_own_quest(&env, &creator, &quest_id)?;
+        let key = DataKey::Verifier(quest_id.clone());
+        env.storage().persistent().set(&key, &public_key);
+        bump_persistent(&env, &key);
+
+        env.events().publish(
+            (symbol_short!("verif_set"), creator),
+            (quest_id, public_key),
+        );
+
+        Ok(())
+    }
+
+    /// Creator: Remove a quest's verifier, re-opening plain completion
+    pub fn remove_verifier(env: Env, creator: Address, quest_id: Symbol) -> Result<(), Error> {
+        creator.require_auth();
+        bump_instance(&env);
+
+        read_own_quest(&env, &creator, &quest_id)?;
+        let key = DataKey::Verifier(quest_id.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(Error::VerifierNotSet);
         }
+        env.storage().persistent().remove(&key);
 
-        // Check if user already completed
-        let mut completions: Map<(Address, Symbol), QuestCompletion> = env
-            .storage()
+        env.events().publish(
+            (symbol_short!("verif_rm"), creator),
+            quest_id,
+        );
+
+        Ok(())
+    }
+
+    /// Get the ed25519 public key verifying a quest
+    pub fn get_verifier(env: Env, quest_id: Symbol) -> Option<BytesN<32>> {
+        read_verifier(&env, &quest_id)
+    }
+
+    /// Check if an attestation nonce has been used for a quest
+    pub fn is_nonce_used(env: Env, quest_id: Symbol, nonce: u64) -> bool {
+        env.storage()
             .persistent()
-            .get(&symbol_short!("completns"))
-            .unwrap_or(Map::new(&env));
+            .has(&DataKey::UsedNonce(quest_id, nonce))
+    }
+
+    /// Creator: Require completions of a quest to be approved by a reviewer
+    pub fn set_review_required(
+        env: Env,
+        creator: Address,
+        quest_id: Symbol,
+        required: bool,
+    ) -> Result<(), Error> {
+        creator.require_auth();
+        bump_instance(&env);
+
+        let mut quest = read_own_quest(&env, &creator, &quest_id)?;
+        quest.review_required = required;
+        write_quest(&env, &quest);
+
+        env.events().publish(
+            (symbol_short!("rev_req"), creator),
+            (quest_id, required),
+        );
+
+        Ok(())
+    }
+
+    /// Creator: Allow an address to approve or reject submissions of a quest
+    pub fn add_reviewer(
+        env: Env,
+        creator: Address,
+        quest_id: Symbol,
+        reviewer: Address,
+    ) -> Result<(), Error> {
+        creator.require_auth();
+        bump_instance(&env);
+
+        read_own_quest(&env, &creator, &quest_id)?;
+        let key = DataKey::Reviewer(quest_id.clone(), reviewer.clone());
+        env.storage().persistent().set(&key, &true);
+        bump_persistent(&env, &key);
 
-        if completions.get((user.clone(), quest_id.clone())).is_some() {
-            panic!("User already completed this quest");
+        env.events().publish(
+            (symbol_short!("rev_add"), creator),
+            (quest_id, reviewer),
+        );
+
+        Ok(())
+    }
+
+    /// Creator: Revoke a reviewer of a quest
+    pub fn remove_reviewer(
+        env: Env,
+        creator: Address,
+        quest_id: Symbol,
+        reviewer: Address,
+    ) -> Result<(), Error> {
+        creator.require_auth();
+        bump_instance(&env);
+
+        read_own_quest(&env, &creator, &quest_id)?;
+        let key = DataKey::Reviewer(quest_id.clone(), reviewer.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(Error::NotReviewer);
         }
+        env.storage().persistent().remove(&key);
 
-        let current_time = env.ledger().timestamp();
+        env.events().publish(
+            (symbol_short!("rev_rm"), creator),
+            (quest_id, reviewer),
+        );
+
+        Ok(())
+    }
 
-        // Update quest completion count
-        quest.current_completions += 1;
-        if let Some(max) = quest.max_completions {
-            if quest.current_completions >= max {
-                quest.status = symbol_short!("completed");
+    /// Check if an address may review submissions of a quest (the creator always can)
+    pub fn is_reviewer(env: Env, quest_id: Symbol, reviewer: Address) -> bool {
+        read_quest(&env, &quest_id).is_some_and(|quest| can_review(&env, &quest, &reviewer))
+    }
+
+    /// Submit proof of completing a quest for review
+    /// A rejected user may submit again; rewards are only paid on approval
+    /// `proof` is the proof of eligibility, not the proof of completion
+    pub fn submit_completion(
+        env: Env,
+        user: Address,
+        quest_id: Symbol,
+        proof_hash: BytesN<32>,
+        proof_uri: String,
+        proof: EligibilityProof,
+    ) -> Result<(), Error> {
+        user.require_auth();
+        bump_instance(&env);
+
+        let quest = read_quest(&env, &quest_id).ok_or(Error::QuestNotFound)?;
+        if !quest.review_required {
+            return Err(Error::ReviewNotRequired);
+        }
+        check_completable(&env, &quest, &user, &proof)?;
+
+        match read_submission(&env, &user, &quest_id) {
+            Some(previous) if previous.status == symbol_short!("pending") => {
+                return Err(Error::SubmissionPending);
             }
+            Some(_) => {}
+            None => add_submission_entry(&env, &quest_id, &user),
         }
 
-        // Record completion
-        let completion = QuestCompletion {
-            user: user.clone(),
-            quest_id: quest_id.clone(),
-            completed_at: current_time,
-            reward_claimed: false,
-        };
+        write_submission(
+            &env,
+            &Submission {
+                user: user.clone(),
+                quest_id: quest_id.clone(),
+                proof_hash: proof_hash.clone(),
+                proof_uri,
+                status: symbol_short!("pending"),
+                submitted_at: env.ledger().timestamp(),
+                reviewed_by: None,
+                reviewed_at: None,
+            },
+        );
+
+        env.events().publish(
+            (symbol_short!("sub_new"), user),
+            (quest_id, proof_hash),
+        );
+
+        Ok(())
+    }
+
+    /// Reviewer: Approve a pending submission, paying the reward and awarding the badge
+    /// Submissions made before the quest expired can still be approved afterwards
+    pub fn approve_submission(
+        env: Env,
+        reviewer: Address,
+        user: Address,
+        quest_id: Symbol,
+    ) -> Result<(), Error> {
+        reviewer.require_auth();
+        bump_instance(&env);
+
+        let (quest, mut submission) = read_pending_submission(&env, &reviewer, &user, &quest_id)?;
+        check_open(&quest)?;
 
-        completions.set((user.clone(), quest_id.clone()), completion);
-        env.storage().persistent().set(&symbol_short!("completns"), &completions);
+        submission.status = symbol_short!("approved");
+        submission.reviewed_by = Some(reviewer.clone());
+        submission.reviewed_at = Some(env.ledger().timestamp());
+        write_submission(&env, &submission);
 
-        // Note: Token transfer should be handled separately or via reward_token contract
-        // For now, we just record the completion. Token transfer can be done via:
-        // 1. Frontend calling reward_token contract directly
-        // 2. Separate function that processes pending rewards
-        // 3. Integration with Stellar Asset Contract
-        
-        // Reward amount is stored in quest, and completion is recorded
-        // Token distribution can be handled in a separate step
+        finish_completion(&env, quest, &user)?;
+
+        env.events().publish(
+            (symbol_short!("sub_appr"), reviewer),
+            (quest_id, user),
+        );
+
+        Ok(())
+    }
+
+    /// Reviewer: Reject a pending submission, the user may submit again
+    pub fn reject_submission(
+        env: Env,
+        reviewer: Address,
+        user: Address,
+        quest_id: Symbol,
+    ) -> Result<(), Error> {
+        reviewer.require_auth();
+        bump_instance(&env);
 
-        // Update completion to mark reward as claimed
-        let reward_amount = quest.reward_amount;
-        let mut completion = completions.get((user.clone(), quest_id.clone())).unwrap();
-        completion.reward_claimed = true;
-        completions.set((user.clone(), quest_id.clone()), completion);
-        env.storage().persistent().set(&symbol_short!("completns"), &completions);
+        let (_, mut submission) = read_pending_submission(&env, &reviewer, &user, &quest_id)?;
 
-        quests.set(quest_id.clone(), quest);
-        env.storage().instance().set(&symbol_short!("quests"), &quests);
+        submission.status = symbol_short!("rejected");
+        submission.reviewed_by = Some(reviewer.clone());
+        submission.reviewed_at = Some(env.ledger().timestamp());
+        write_submission(&env, &submission);
 
         env.events().publish(
-            (symbol_short!("quest_dn"), user),
-            (quest_id, reward_amount),
+            (symbol_short!("sub_rej"), reviewer),
+            (quest_id, user),
         );
 
-        true
+        Ok(())
+    }
+
+    /// Get a user's latest submission for a quest
+    pub fn get_submission(env: Env, user: Address, quest_id: Symbol) -> Option<Submission> {
+        read_submission(&env, &user, &quest_id)
+    }
+
+    /// Get a page of a quest's submissions in the order users first submitted
+    /// At most 50 submissions are returned per call
+    pub fn get_submissions(env: Env, quest_id: Symbol, offset: u32, limit: u32) -> Vec<Submission> {
+        let mut submissions = Vec::new(&env);
+        let count = read_submission_count(&env, &quest_id);
+        let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+
+        for pos in offset..end {
+            let key = DataKey::SubmissionEntry(quest_id.clone(), pos);
+            let user: Address = env.storage().persistent().get(&key).unwrap();
+            bump_persistent(&env, &key);
+            submissions.push_back(read_submission(&env, &user, &quest_id).unwrap());
+        }
+        submissions
+    }
+
+    /// Get number of users who submitted to a quest
+    pub fn get_submission_count(env: Env, quest_id: Symbol) -> u32 {
+        read_submission_count(&env, &quest_id)
     }
 
     /// Get quest details
     pub fn get_quest(env: Env, quest_id: Symbol) -> Option<Quest> {
-        let quests: Map<Symbol, Quest> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("quests"))
-            .unwrap_or(Map::new(&env));
+        read_quest(&env, &quest_id)
+    }
 
-        quests.get(quest_id)
+    /// Creator: Set who may complete a quest, normally before activate_quest
+    /// Badge-gated policies need the BadgeNFT contract to be set, and a QuestBadge gate a quest that awards a badge
+    pub fn set_eligibility(
+        env: Env,
+        creator: Address,
+        quest_id: Symbol,
+        eligibility: Eligibility,
+    ) -> Result<(), Error> {
+        creator.require_auth();
+        bump_instance(&env);
+
+        let mut quest = read_own_quest(&env, &creator, &quest_id)?;
+        if let Eligibility::BadgeClass(_) | Eligibility::QuestBadge(_) = eligibility {
+            read_badge_contract(&env)?;
+        }
+        if let Eligibility::QuestBadge(gate) = &eligibility {
+            let gate = read_quest(&env, gate).ok_or(Error::QuestNotFound)?;
+            if gate.badge_id.is_none() {
+                return Err(Error::QuestAwardsNoBadge);
+            }
+        }
+        quest.eligibility = eligibility.clone();
+        write_quest(&env, &quest);
+
+        env.events().publish(
+            (symbol_short!("elig_set"), creator),
+            (quest_id, eligibility),
+        );
+
+        Ok(())
+    }
+
+    /// Creator: Add users to or remove them from a quest's allowlist or denylist
+    /// The same list backs both policies, so switching policy flips its meaning
+    pub fn set_listed(
+        env: Env,
+        creator: Address,
+        quest_id: Symbol,
+        users: Vec<Address>,
+        listed: bool,
+    ) -> Result<(), Error> {
+        creator.require_auth();
+        bump_instance(&env);
+
+        read_own_quest(&env, &creator, &quest_id)?;
+        for user in users.iter() {
+            let key = DataKey::Listed(quest_id.clone(), user);
+            if listed {
+                env.storage().persistent().set(&key, &true);
+                bump_persistent(&env, &key);
+            } else {
+                env.storage().persistent().remove(&key);
+            }
+        }
+
+        env.events().publish(
+            (symbol_short!("list_set"), creator),
+            (quest_id, users, listed),
+        );
+
+        Ok(())
+    }
+
+    /// Check if an address is on a quest's allowlist or denylist
+    pub fn is_listed(env: Env, quest_id: Symbol, user: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Listed(quest_id, user))
+    }
+
+    /// Check if a user passes a quest's eligibility policy and has completed its prerequisites
+    /// `proof` is the user's Merkle proof or badge on gated quests and `None` otherwise
+    /// Status, expiry and remaining slots are not considered
+    pub fn is_eligible(
+        env: Env,
+        user: Address,
+        quest_id: Symbol,
+        proof: EligibilityProof,
+    ) -> Result<bool, Error> {
+        let quest = read_quest(&env, &quest_id).ok_or(Error::QuestNotFound)?;
+        match check_eligible(&env, &quest, &user, &proof) {
+            Ok(()) => Ok(true),
+            Err(Error::NotEligible) | Err(Error::PrerequisiteNotMet) => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Get a quest and every quest it depends on, breadth-first from the quest itself
+    /// Each node lists its direct prerequisites so the UI can rebuild the tree
+    pub fn get_quest_chain(env: Env, quest_id: Symbol) -> Result<Vec<QuestChainNode>, Error> {
+        let quest = read_quest(&env, &quest_id).ok_or(Error::QuestNotFound)?;
+        read_quest_chain(&env, &quest_id, &quest.prerequisites)
     }
 
     /// Get user's completion status for a quest
     pub fn has_completed(env: Env, user: Address, quest_id: Symbol) -> bool {
-        let completions: Map<(Address, Symbol), QuestCompletion> = env
-            .storage()
-            .persistent()
-            .get(&symbol_short!("completns"))
-            .unwrap_or(Map::new(&env));
+        read_completion(&env, &user, &quest_id).is_some()
+    }
 
-        completions.get((user, quest_id)).is_some()
+    /// Get a page of active quests that have not expired
+    /// At most 50 quests are returned per call, fewer when the page holds expired quests
+    pub fn get_active_quests(env: Env, offset: u32, limit: u32) -> Vec<Quest> {
+        let now = env.ledger().timestamp();
+        let mut live = Vec::new(&env);
+        for quest in read_quest_page(&env, &symbol_short!("active"), offset, limit).iter() {
+            if quest.expires_at.is_none_or(|expires_at| now <= expires_at) {
+                live.push_back(quest);
+            }
+        }
+        live
     }
 
-    /// Get all active quests
-    /// Note: Map iteration is not directly supported, so this returns empty Vec
-    /// In production, maintain a separate Vec<Symbol> of active quest IDs
-    pub fn get_active_quests(env: Env) -> Vec<Quest> {
-        // Map iteration not available in Soroban SDK
-        // For now, return empty - in production use Vec<Symbol> for active quest IDs
-        Vec::new(&env)
+    /// Get a page of quests in a status ("draft", "active", "completed", "cancelled")
+    /// At most 50 quests are returned per call; expired quests stay listed as active
+    pub fn get_quests_by_status(env: Env, status: Symbol, offset: u32, limit: u32) -> Vec<Quest> {
+        read_quest_page(&env, &status, offset, limit)
     }
 
-    /// Get user's completed quests
-    /// Note: Map iteration is not directly supported
-    /// In production, maintain a Vec<Symbol> per user for completed quests
-    pub fn get_user_completions(env: Env, _user: Address) -> Vec<Symbol> {
-        // Map iteration not available - for now return empty
-        // In production, store Vec<Symbol> per user for completions
-        Vec::new(&env)
+    /// Get number of quests in a status
+    pub fn get_status_count(env: Env, status: Symbol) -> u32 {
+        read_status_count(&env, &status)
     }
 
-    /// Get quest leaderboard (users with most completions)
-    /// Note: Map iteration is not directly supported
-    /// In production, maintain a separate leaderboard Vec<(Address, i128)>
-    pub fn get_leaderboard(env: Env) -> Vec<(Address, i128)> {
-        // Map iteration not available - for now return empty
-        // In production, maintain a sorted Vec<(Address, i128)> for leaderboard
-        Vec::new(&env)
+    /// Get a page of a user's completions, oldest first
+    /// At most 50 completions are returned per call
+    pub fn get_user_completions(env: Env, user: Address, offset: u32, limit: u32) -> Vec<QuestCompletion> {
+        let mut completions = Vec::new(&env);
+        let count = read_user_stats(&env, &user).total_completions;
+        let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+
+        for pos in offset..end {
+            let key = DataKey::UserCompletion(user.clone(), pos);
+            let quest_id: Symbol = env.storage().persistent().get(&key).unwrap();
+            bump_persistent(&env, &key);
+            completions.push_back(read_completion(&env, &user, &quest_id).unwrap());
+        }
+        completions
     }
 
-    /// Admin: Cancel a quest
-    pub fn cancel_quest(env: Env, quest_id: Symbol) {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("admin"))
-            .unwrap();
+    /// Get user's total completions and rewards earned
+    pub fn get_user_stats(env: Env, user: Address) -> UserStats {
+        read_user_stats(&env, &user)
+    }
+
+    /// Get quest leaderboard (users with most completions, then most rewards)
+    pub fn get_leaderboard(env: Env) -> Vec<LeaderboardEntry> {
+        read_leaderboard(&env)
+    }
+
+    /// Get a user's 1-based leaderboard rank, None if outside the top-N
+    pub fn get_rank(env: Env, user: Address) -> Option<u32> {
+        read_leaderboard(&env)
+            .iter()
+            .position(|entry| entry.user == user)
+            .map(|pos| pos as u32 + 1)
+    }
+
+    /// Get the number of leaderboard slots
+    pub fn get_leaderboard_size(env: Env) -> u32 {
+        read_leaderboard_size(&env)
+    }
+
+    /// Admin: Set the number of leaderboard slots
+    /// Shrinking drops the lowest ranks, growing fills up as users complete quests
+    pub fn set_leaderboard_size(env: Env, size: u32) -> Result<(), Error> {
+        let admin = read_admin(&env)?;
         admin.require_auth();
 
-        let mut quests: Map<Symbol, Quest> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("quests"))
-            .unwrap_or(Map::new(&env));
+        if size == 0 || size > MAX_LEADERBOARD_SIZE {
+            return Err(Error::InvalidLeaderboardSize);
+        }
+        env.storage().instance().set(&symbol_short!("lb_size"), &size);
 
-        let mut quest = quests.get(quest_id.clone()).unwrap();
-        quest.status = symbol_short!("cancelled");
-        quests.set(quest_id.clone(), quest);
-        env.storage().instance().set(&symbol_short!("quests"), &quests);
+        let mut board = read_leaderboard(&env);
+        if board.len() > size {
+            while board.len() > size {
+                board.pop_back();
+            }
+            write_leaderboard(&env, &board);
+        }
+
+        env.events().publish(
+            (symbol_short!("lb_size"), admin),
+            size,
+        );
+
+        Ok(())
+    }
+
+    /// Admin: Cancel a quest
+    pub fn cancel_quest(env: Env, quest_id: Symbol) -> Result<(), Error> {
+        let admin = read_admin(&env)?;
+        admin.require_auth();
+        bump_instance(&env);
+
+        let mut quest = read_quest(&env, &quest_id).ok_or(Error::QuestNotFound)?;
+        if quest.status == symbol_short!("cancelled") {
+            return Err(Error::QuestAlreadyCancelled);
+        }
+        refund_and_cancel(&env, &mut quest);
 
         env.events().publish(
             (symbol_short!("quest_cn"), admin),
             quest_id,
         );
+
+        Ok(())
+    }
+
+    /// Creator: Take back the unused escrow of a quest that expired or never left draft
+    /// The quest is cancelled; returns the amount refunded
+    pub fn reclaim_escrow(env: Env, creator: Address, quest_id: Symbol) -> Result<i128, Error> {
+        creator.require_auth();
+        bump_instance(&env);
+
+        let mut quest = read_own_quest(&env, &creator, &quest_id)?;
+        if quest.status == symbol_short!("cancelled") {
+            return Err(Error::QuestAlreadyCancelled);
+        }
+        if quest.status == symbol_short!("active") {
+            let now = env.ledger().timestamp();
+            if quest.expires_at.is_none_or(|expires_at| now <= expires_at) {
+                return Err(Error::QuestNotExpired);
+            }
+        } else if quest.status != symbol_short!("draft") {
+            return Err(Error::QuestNotActive);
+        }
+        let refund = refund_and_cancel(&env, &mut quest);
+
+        env.events().publish(
+            (symbol_short!("quest_rcl"), creator),
+            (quest_id, refund),
+        );
+
+        Ok(refund)
     }
 
     /// Get total quest count
@@ -295,6 +1513,93 @@ impl QuestPlatform {
             .get(&symbol_short!("quest_cnt"))
             .unwrap_or(0)
     }
+
+    /// Admin: Set the BadgeNFT contract used to award quest badges
+    /// The quest platform must be its registered platform or an approved minter
+    pub fn set_badge_contract(env: Env, badge_contract: Address) -> Result<(), Error> {
+        let admin = read_admin(&env)?;
+        admin.require_auth();
+
+        env.storage().instance().set(&symbol_short!("badge_nft"), &badge_contract);
+
+        env.events().publish(
+            (symbol_short!("badge_set"), admin),
+            badge_contract,
+        );
+
+        Ok(())
+    }
+
+    /// Get the BadgeNFT contract used to award quest badges
+    pub fn get_badge_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("badge_nft"))
+    }
+
+    /// Admin: Propose a new admin, who must accept before taking over
+    pub fn propose_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        let admin = read_admin(&env)?;
+        admin.require_auth();
+
+        env.storage().instance().set(&symbol_short!("pend_adm"), &new_admin);
+
+        env.events().publish(
+            (symbol_short!("adm_prop"), admin),
+            new_admin,
+        );
+
+        Ok(())
+    }
+
+    /// Accept a pending admin transfer (called by the proposed admin)
+    pub fn accept_admin(env: Env) -> Result<(), Error> {
+        let admin = read_admin(&env)?;
+        let new_admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("pend_adm"))
+            .ok_or(Error::NoPendingAdmin)?;
+        new_admin.require_auth();
+
+        env.storage().instance().set(&symbol_short!("admin"), &new_admin);
+        env.storage().instance().remove(&symbol_short!("pend_adm"));
+
+        env.events().publish(
+            (symbol_short!("adm_acc"), admin),
+            new_admin,
+        );
+
+        Ok(())
+    }
+
+    /// Admin: Cancel a pending admin transfer
+    pub fn cancel_admin_transfer(env: Env) -> Result<(), Error> {
+        let admin = read_admin(&env)?;
+        admin.require_auth();
+
+        let pending: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("pend_adm"))
+            .ok_or(Error::NoPendingAdmin)?;
+        env.storage().instance().remove(&symbol_short!("pend_adm"));
+
+        env.events().publish(
+            (symbol_short!("adm_cncl"), admin),
+            pending,
+        );
+
+        Ok(())
+    }
+
+    /// Get current admin
+    pub fn get_admin(env: Env) -> Result<Address, Error> {
+        read_admin(&env)
+    }
+
+    /// Get the proposed admin awaiting acceptance
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("pend_adm"))
+    }
 }
 
 #[cfg(test)]
@@ -1,38 +1,138 @@
 #![cfg(test)]
+extern crate std;
 
-use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env, Symbol, String};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    token, vec, xdr::ToXdr, Address, BytesN, Env, Map, String, Symbol, TryFromVal,
+};
 use super::*;
 
+fn create_reward_token<'a>(
+    env: &Env,
+    admin: &Address,
+) -> (token::Client<'a>, token::StellarAssetClient<'a>) {
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    (
+        token::Client::new(env, &sac.address()),
+        token::StellarAssetClient::new(env, &sac.address()),
+    )
+}
+
+/// Create a funded quest that is not live yet
+fn create_draft_quest(env: &Env, client: &QuestPlatformClient, creator: &Address, quest_id: &Symbol) {
+    client.create_quest(
+        creator,
+        quest_id,
+        &String::from_str(env, "Test Quest"),
+        &String::from_str(env, "Complete this test quest"),
+        &1000,
+        &None,
+        &None,
+        &Some(3),
+        &Vec::new(env),
+    );
+}
+
+fn create_test_quest(env: &Env, client: &QuestPlatformClient, creator: &Address, quest_id: &Symbol) {
+    create_draft_quest(env, client, creator, quest_id);
+    client.activate_quest(creator, quest_id);
+}
+
 #[test]
 fn test_initialize() {
     let env = Env::default();
+    env.mock_all_auths();
+
     let admin = Address::generate(&env);
     let reward_token = Address::generate(&env);
-    
+
     let contract_id = env.register_contract(None, QuestPlatform);
     let client = QuestPlatformClient::new(&env, &contract_id);
-    
+
     client.initialize(&admin, &reward_token);
-    
+
     assert_eq!(client.get_quest_count(), 0);
 }
 
 #[test]
-fn test_create_quest() {
+fn test_initialize_only_once() {
     let env = Env::default();
+    env.mock_all_auths();
+
     let admin = Address::generate(&env);
-    let creator = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    let reward_token = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &reward_token);
+    assert_eq!(env.auths()[0].0, admin);
+
+    assert_eq!(
+        client.try_initialize(&attacker, &reward_token),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    assert_eq!(client.get_admin(), admin);
+}
+
+#[test]
+fn test_two_step_admin_handover() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let other = Address::generate(&env);
     let reward_token = Address::generate(&env);
-    
+
     let contract_id = env.register_contract(None, QuestPlatform);
     let client = QuestPlatformClient::new(&env, &contract_id);
-    
+
+    assert_eq!(client.try_propose_admin(&new_admin), Err(Ok(Error::NotInitialized)));
     client.initialize(&admin, &reward_token);
-    
+    assert_eq!(client.try_accept_admin(), Err(Ok(Error::NoPendingAdmin)));
+
+    client.propose_admin(&new_admin);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+    assert_eq!(client.get_admin(), admin);
+
+    client.accept_admin();
+    assert_eq!(env.auths()[0].0, new_admin);
+    assert_eq!(client.get_admin(), new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+
+    client.propose_admin(&other);
+    assert_eq!(env.auths()[0].0, new_admin);
+    client.cancel_admin_transfer();
+    assert_eq!(client.get_pending_admin(), None);
+    assert_eq!(client.try_accept_admin(), Err(Ok(Error::NoPendingAdmin)));
+    assert_eq!(client.try_cancel_admin_transfer(), Err(Ok(Error::NoPendingAdmin)));
+    assert_eq!(client.get_admin(), new_admin);
+}
+
+#[test]
+fn test_create_quest() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &100_000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &token.address);
+
     let quest_id = symbol_short!("quest1");
     let title = String::from_str(&env, "Test Quest");
     let description = String::from_str(&env, "Complete this test quest");
-    
+
     client.create_quest(
         &creator,
         &quest_id,
@@ -42,11 +142,1754 @@ fn test_create_quest() {
         &None,
         &None,
         &Some(100),
+        &Vec::new(&env),
     );
-    
+
     let quest = client.get_quest(&quest_id).unwrap();
     assert_eq!(quest.id, quest_id);
     assert_eq!(quest.creator, creator);
-    assert_eq!(quest.status, symbol_short!("active"));
+    assert_eq!(quest.reward_token, token.address);
+    assert_eq!(quest.status, symbol_short!("draft"));
+    assert_eq!(client.get_active_quests(&0, &10).len(), 0);
+
+    client.activate_quest(&creator, &quest_id);
+    assert_eq!(env.auths()[0].0, creator);
+    assert_eq!(client.get_quest(&quest_id).unwrap().status, symbol_short!("active"));
+    assert_eq!(client.get_active_quests(&0, &10).len(), 1);
+
+    // The whole reward pool is escrowed in the platform
+    assert_eq!(token.balance(&creator), 0);
+    assert_eq!(token.balance(&contract_id), 100_000);
+}
+
+#[test]
+fn test_complete_quest_pays_reward() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &5000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    let quest_id = symbol_short!("quest1");
+    create_test_quest(&env, &client, &creator, &quest_id);
+    assert_eq!(token.balance(&creator), 2000);
+    assert_eq!(token.balance(&contract_id), 3000);
+
+    client.complete_quest(&user1, &quest_id, &EligibilityProof::None);
+    assert_eq!(token.balance(&user1), 1000);
+    assert_eq!(token.balance(&contract_id), 2000);
+    assert!(client.has_completed(&user1, &quest_id));
+
+    client.complete_quest(&user2, &quest_id, &EligibilityProof::None);
+    assert_eq!(token.balance(&user2), 1000);
+    assert_eq!(token.balance(&contract_id), 1000);
+
+    let quest = client.get_quest(&quest_id).unwrap();
+    assert_eq!(quest.current_completions, 2);
+}
+
+#[test]
+fn test_cancel_quest_refunds_remaining_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let user = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &3000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    let quest_id = symbol_short!("quest1");
+    create_test_quest(&env, &client, &creator, &quest_id);
+    client.complete_quest(&user, &quest_id, &EligibilityProof::None);
+
+    client.cancel_quest(&quest_id);
+    assert_eq!(token.balance(&user), 1000);
+    assert_eq!(token.balance(&creator), 2000);
+    assert_eq!(token.balance(&contract_id), 0);
+    assert_eq!(client.get_quest(&quest_id).unwrap().status, symbol_short!("cancelled"));
+}
+
+#[test]
+fn test_creator_reclaims_escrow_after_expiry() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let user = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &9000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    let timed = symbol_short!("timed");
+    client.create_quest(
+        &creator,
+        &timed,
+        &String::from_str(&env, "Timed Quest"),
+        &String::from_str(&env, "Finish before the deadline"),
+        &1000,
+        &None,
+        &Some(100),
+        &Some(3),
+        &Vec::new(&env),
+    );
+    client.activate_quest(&creator, &timed);
+    client.complete_quest(&user, &timed, &EligibilityProof::None);
+
+    assert_eq!(client.try_reclaim_escrow(&creator, &timed), Err(Ok(Error::QuestNotExpired)));
+    assert_eq!(client.try_reclaim_escrow(&user, &timed), Err(Ok(Error::NotQuestCreator)));
+
+    env.ledger().with_mut(|li| li.timestamp = 101);
+    assert_eq!(client.reclaim_escrow(&creator, &timed), 2000);
+    assert_eq!(env.auths()[0].0, creator);
+    assert_eq!(token.balance(&creator), 8000);
+    assert_eq!(client.get_quest(&timed).unwrap().status, symbol_short!("cancelled"));
+    assert_eq!(client.try_reclaim_escrow(&creator, &timed), Err(Ok(Error::QuestAlreadyCancelled)));
+
+    // Quests without a deadline only while they are still drafts
+    let open = symbol_short!("open");
+    create_test_quest(&env, &client, &creator, &open);
+    assert_eq!(client.try_reclaim_escrow(&creator, &open), Err(Ok(Error::QuestNotExpired)));
+    let draft = symbol_short!("draft");
+    create_draft_quest(&env, &client, &creator, &draft);
+    assert_eq!(client.reclaim_escrow(&creator, &draft), 3000);
+    assert_eq!(token.balance(&creator), 5000);
+    assert_eq!(token.balance(&contract_id), 3000);
+    assert_eq!(
+        client.try_reclaim_escrow(&creator, &symbol_short!("missing")),
+        Err(Ok(Error::QuestNotFound))
+    );
+}
+
+#[test]
+fn test_create_quest_without_funds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &2999);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    let result = client.try_create_quest(
+        &creator,
+        &symbol_short!("quest1"),
+        &String::from_str(&env, "Test Quest"),
+        &String::from_str(&env, "Complete this test quest"),
+        &1000,
+        &None,
+        &None,
+        &Some(3),
+        &Vec::new(&env),
+    );
+    assert!(result.is_err());
+    assert_eq!(client.get_quest(&symbol_short!("quest1")), None);
+    assert_eq!(token.balance(&creator), 2999);
+}
+
+#[test]
+fn test_create_quest_errors() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &100_000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+
+    let quest_id = symbol_short!("quest1");
+    let title = String::from_str(&env, "Test Quest");
+    let description = String::from_str(&env, "Complete this test quest");
+    let create = |reward: i128, max: Option<i128>| {
+        client.try_create_quest(
+            &creator, &quest_id, &title, &description, &reward, &None, &None, &max, &Vec::new(&env),
+        )
+    };
+
+    assert_eq!(create(1000, Some(3)), Err(Ok(Error::NotInitialized)));
+
+    client.initialize(&admin, &token.address);
+    assert_eq!(create(-1, Some(3)), Err(Ok(Error::NegativeRewardAmount)));
+    assert_eq!(create(1000, None), Err(Ok(Error::InvalidMaxCompletions)));
+    assert_eq!(create(1000, Some(0)), Err(Ok(Error::InvalidMaxCompletions)));
+    assert_eq!(create(i128::MAX, Some(2)), Err(Ok(Error::RewardOverflow)));
+
+    assert_eq!(create(1000, Some(3)), Ok(Ok(true)));
+    assert_eq!(create(1000, Some(3)), Err(Ok(Error::QuestAlreadyExists)));
+}
+
+#[test]
+fn test_complete_quest_errors() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let user = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &100_000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    assert_eq!(
+        client.try_complete_quest(&user, &symbol_short!("missing"), &EligibilityProof::None),
+        Err(Ok(Error::QuestNotFound))
+    );
+
+    let quest_id = symbol_short!("quest1");
+    create_test_quest(&env, &client, &creator, &quest_id);
+    client.complete_quest(&user, &quest_id, &EligibilityProof::None);
+    assert_eq!(
+        client.try_complete_quest(&user, &quest_id, &EligibilityProof::None),
+        Err(Ok(Error::AlreadyCompleted))
+    );
+
+    client.cancel_quest(&quest_id);
+    assert_eq!(
+        client.try_complete_quest(&Address::generate(&env), &quest_id, &EligibilityProof::None),
+        Err(Ok(Error::QuestNotActive))
+    );
+    assert_eq!(client.try_cancel_quest(&quest_id), Err(Ok(Error::QuestAlreadyCancelled)));
+    assert_eq!(
+        client.try_cancel_quest(&symbol_short!("missing")),
+        Err(Ok(Error::QuestNotFound))
+    );
+
+    let timed = symbol_short!("timed");
+    client.create_quest(
+        &creator,
+        &timed,
+        &String::from_str(&env, "Timed Quest"),
+        &String::from_str(&env, "Finish before the deadline"),
+        &1000,
+        &None,
+        &Some(500),
+        &Some(3),
+        &Vec::new(&env),
+    );
+    client.activate_quest(&creator, &timed);
+    env.ledger().with_mut(|li| li.timestamp = 501);
+    assert_eq!(client.try_complete_quest(&user, &timed, &EligibilityProof::None), Err(Ok(Error::QuestExpired)));
+}
+
+fn submit(env: &Env, client: &QuestPlatformClient, user: &Address, quest_id: &Symbol, seed: u8) {
+    client.submit_completion(
+        user,
+        quest_id,
+        &BytesN::from_array(env, &[seed; 32]),
+        &String::from_str(env, "ipfs://proof"),
+        &EligibilityProof::None,
+    );
+}
+
+#[test]
+fn test_submission_review_flow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let reviewer = Address::generate(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &3000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    let quest_id = symbol_short!("quest1");
+    create_test_quest(&env, &client, &creator, &quest_id);
+    client.set_review_required(&creator, &quest_id, &true);
+    assert_eq!(env.auths()[0].0, creator);
+    client.add_reviewer(&creator, &quest_id, &reviewer);
+    assert!(client.is_reviewer(&quest_id, &reviewer));
+    assert!(client.is_reviewer(&quest_id, &creator));
+
+    assert_eq!(client.try_complete_quest(&user1, &quest_id, &EligibilityProof::None), Err(Ok(Error::ReviewRequired)));
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    submit(&env, &client, &user1, &quest_id, 1);
+    submit(&env, &client, &user2, &quest_id, 2);
+    assert_eq!(env.auths()[0].0, user2);
+
+    // Nothing is paid while submissions are pending
+    let submission = client.get_submission(&user1, &quest_id).unwrap();
+    assert_eq!(submission.status, symbol_short!("pending"));
+    assert_eq!(submission.proof_hash, BytesN::from_array(&env, &[1; 32]));
+    assert_eq!(submission.submitted_at, 100);
+    assert_eq!(token.balance(&user1), 0);
+    assert!(!client.has_completed(&user1, &quest_id));
+
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    client.approve_submission(&reviewer, &user1, &quest_id);
+    assert_eq!(env.auths()[0].0, reviewer);
+    assert_eq!(token.balance(&user1), 1000);
+    assert!(client.has_completed(&user1, &quest_id));
+    let submission = client.get_submission(&user1, &quest_id).unwrap();
+    assert_eq!(submission.status, symbol_short!("approved"));
+    assert_eq!(submission.reviewed_by, Some(reviewer.clone()));
+    assert_eq!(submission.reviewed_at, Some(200));
+
+    // A rejected user can resubmit and is approved by the creator
+    client.reject_submission(&creator, &user2, &quest_id);
+    assert_eq!(token.balance(&user2), 0);
+    assert_eq!(
+        client.get_submission(&user2, &quest_id).unwrap().status,
+        symbol_short!("rejected")
+    );
+    submit(&env, &client, &user2, &quest_id, 3);
+    client.approve_submission(&creator, &user2, &quest_id);
+    assert_eq!(token.balance(&user2), 1000);
+
+    let submissions = client.get_submissions(&quest_id, &0, &10);
+    assert_eq!(client.get_submission_count(&quest_id), 2);
+    assert_eq!(submissions.len(), 2);
+    assert_eq!(submissions.get(0).unwrap().user, user1);
+    assert_eq!(submissions.get(1).unwrap().user, user2);
+    assert_eq!(submissions.get(1).unwrap().proof_hash, BytesN::from_array(&env, &[3; 32]));
+    assert_eq!(client.get_quest(&quest_id).unwrap().current_completions, 2);
+}
+
+#[test]
+fn test_reviewed_quest_never_completes_directly() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let user = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &3000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    // The escrow is funded while the quest is still a draft, nobody can claim it yet
+    let quest_id = symbol_short!("quest1");
+    create_draft_quest(&env, &client, &creator, &quest_id);
+    assert_eq!(token.balance(&contract_id), 3000);
+    assert_eq!(client.try_complete_quest(&user, &quest_id, &EligibilityProof::None), Err(Ok(Error::QuestNotActive)));
+
+    client.set_review_required(&creator, &quest_id, &true);
+    assert_eq!(
+        client.try_activate_quest(&user, &quest_id),
+        Err(Ok(Error::NotQuestCreator))
+    );
+    client.activate_quest(&creator, &quest_id);
+    assert_eq!(
+        client.try_activate_quest(&creator, &quest_id),
+        Err(Ok(Error::QuestNotDraft))
+    );
+    assert_eq!(client.try_complete_quest(&user, &quest_id, &EligibilityProof::None), Err(Ok(Error::ReviewRequired)));
+    assert_eq!(token.balance(&user), 0);
+
+    submit(&env, &client, &user, &quest_id, 1);
+    client.approve_submission(&creator, &user, &quest_id);
+    assert_eq!(token.balance(&user), 1000);
+}
+
+#[test]
+fn test_submission_errors() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let reviewer = Address::generate(&env);
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &3000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    let quest_id = symbol_short!("quest1");
+    create_test_quest(&env, &client, &creator, &quest_id);
+    assert_eq!(
+        client.try_set_review_required(&other, &quest_id, &true),
+        Err(Ok(Error::NotQuestCreator))
+    );
+    assert_eq!(
+        client.try_add_reviewer(&other, &quest_id, &reviewer),
+        Err(Ok(Error::NotQuestCreator))
+    );
+    assert_eq!(
+        client.try_remove_reviewer(&creator, &quest_id, &reviewer),
+        Err(Ok(Error::NotReviewer))
+    );
+
+    // Direct completions are only possible while review is off
+    client.complete_quest(&other, &quest_id, &EligibilityProof::None);
+    assert_eq!(
+        client.try_submit_completion(
+            &user,
+            &quest_id,
+            &BytesN::from_array(&env, &[1; 32]),
+            &String::from_str(&env, "ipfs://proof"),
+            &EligibilityProof::None,
+        ),
+        Err(Ok(Error::ReviewNotRequired))
+    );
+    client.set_review_required(&creator, &quest_id, &true);
+    assert_eq!(
+        client.try_submit_completion(
+            &other,
+            &quest_id,
+            &BytesN::from_array(&env, &[2; 32]),
+            &String::from_str(&env, "ipfs://proof"),
+            &EligibilityProof::None,
+        ),
+        Err(Ok(Error::AlreadyCompleted))
+    );
+
+    assert_eq!(
+        client.try_approve_submission(&creator, &user, &quest_id),
+        Err(Ok(Error::SubmissionNotFound))
+    );
+    submit(&env, &client, &user, &quest_id, 1);
+    assert_eq!(
+        client.try_submit_completion(
+            &user,
+            &quest_id,
+            &BytesN::from_array(&env, &[1; 32]),
+            &String::from_str(&env, "ipfs://proof"),
+            &EligibilityProof::None,
+        ),
+        Err(Ok(Error::SubmissionPending))
+    );
+
+    client.add_reviewer(&creator, &quest_id, &reviewer);
+    client.remove_reviewer(&creator, &quest_id, &reviewer);
+    assert_eq!(
+        client.try_approve_submission(&reviewer, &user, &quest_id),
+        Err(Ok(Error::NotReviewer))
+    );
+    assert_eq!(
+        client.try_reject_submission(&other, &user, &quest_id),
+        Err(Ok(Error::NotReviewer))
+    );
+
+    // A user who completed directly while their submission was pending is not paid twice
+    client.set_review_required(&creator, &quest_id, &false);
+    client.complete_quest(&user, &quest_id, &EligibilityProof::None);
+    client.set_review_required(&creator, &quest_id, &true);
+    assert_eq!(
+        client.try_approve_submission(&creator, &user, &quest_id),
+        Err(Ok(Error::AlreadyCompleted))
+    );
+    assert_eq!(token.balance(&user), 1000);
+    assert_eq!(client.get_user_stats(&user).total_completions, 1);
+    assert_eq!(client.get_quest(&quest_id).unwrap().current_completions, 2);
+
+    // Approval fails once the quest no longer has reward slots open
+    let last = Address::generate(&env);
+    let late = Address::generate(&env);
+    submit(&env, &client, &last, &quest_id, 3);
+    submit(&env, &client, &late, &quest_id, 4);
+    client.approve_submission(&creator, &last, &quest_id);
+    assert_eq!(
+        client.try_reject_submission(&creator, &last, &quest_id),
+        Err(Ok(Error::SubmissionNotPending))
+    );
+    assert_eq!(
+        client.try_approve_submission(&creator, &late, &quest_id),
+        Err(Ok(Error::QuestNotActive))
+    );
+    assert_eq!(token.balance(&late), 0);
+}
+
+/// Sign an attestation the way the game backend does
+fn attest(
+    env: &Env,
+    key: &SigningKey,
+    platform: &Address,
+    user: &Address,
+    quest_id: &Symbol,
+    nonce: u64,
+    expiry: u64,
+) -> BytesN<64> {
+    let payload = (
+        String::from_str(env, "quest_platform:attestation:v1"),
+        platform.clone(),
+        quest_id.clone(),
+        user.clone(),
+        nonce,
+        expiry,
+    )
+        .to_xdr(env);
+    let mut message = std::vec![0u8; payload.len() as usize];
+    payload.copy_into_slice(&mut message);
+    BytesN::from_array(env, &key.sign(&message).to_bytes())
+}
+
+#[test]
+fn test_complete_quest_with_attestation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &3000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    // The verifier is registered before the quest goes live, so there is no window for plain completions
+    let quest_id = symbol_short!("quest1");
+    create_draft_quest(&env, &client, &creator, &quest_id);
+
+    let verifier = SigningKey::from_bytes(&[7; 32]);
+    let public_key = BytesN::from_array(&env, &verifier.verifying_key().to_bytes());
+    client.set_verifier(&creator, &quest_id, &public_key);
+    assert_eq!(env.auths()[0].0, creator);
+    assert_eq!(client.get_verifier(&quest_id), Some(public_key));
+    assert_eq!(client.try_complete_quest(&user1, &quest_id, &EligibilityProof::None), Err(Ok(Error::AttestationRequired)));
+    let signature = attest(&env, &verifier, &contract_id, &user1, &quest_id, 9, 1100);
+    assert_eq!(
+        client.try_complete_quest_with_attestation(&user1, &quest_id, &9, &1100, &signature, &EligibilityProof::None),
+        Err(Ok(Error::QuestNotActive))
+    );
+    client.activate_quest(&creator, &quest_id);
+
+    // Verified quests can no longer be completed without proof
+    assert_eq!(client.try_complete_quest(&user1, &quest_id, &EligibilityProof::None), Err(Ok(Error::AttestationRequired)));
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    let signature = attest(&env, &verifier, &contract_id, &user1, &quest_id, 1, 1100);
+    client.complete_quest_with_attestation(&user1, &quest_id, &1, &1100, &signature, &EligibilityProof::None);
+    assert_eq!(env.auths()[0].0, user1);
+    assert_eq!(token.balance(&user1), 1000);
+    assert!(client.has_completed(&user1, &quest_id));
+    assert!(client.is_nonce_used(&quest_id, &1));
+
+    // The same nonce cannot be replayed, even for another user
+    let signature = attest(&env, &verifier, &contract_id, &user2, &quest_id, 1, 1100);
+    assert_eq!(
+        client.try_complete_quest_with_attestation(&user2, &quest_id, &1, &1100, &signature, &EligibilityProof::None),
+        Err(Ok(Error::NonceAlreadyUsed))
+    );
+
+    // Attestations lapse at their expiry
+    let signature = attest(&env, &verifier, &contract_id, &user2, &quest_id, 2, 999);
+    assert_eq!(
+        client.try_complete_quest_with_attestation(&user2, &quest_id, &2, &999, &signature, &EligibilityProof::None),
+        Err(Ok(Error::AttestationExpired))
+    );
+
+    // Signatures by another key, or over other fields, are rejected
+    let forger = SigningKey::from_bytes(&[8; 32]);
+    let forged = attest(&env, &forger, &contract_id, &user2, &quest_id, 3, 1100);
+    assert!(client
+        .try_complete_quest_with_attestation(&user2, &quest_id, &3, &1100, &forged, &EligibilityProof::None)
+        .is_err());
+    let signature = attest(&env, &verifier, &contract_id, &user2, &quest_id, 3, 1100);
+    assert!(client
+        .try_complete_quest_with_attestation(&user2, &quest_id, &3, &2000, &signature, &EligibilityProof::None)
+        .is_err());
+    assert!(!client.is_nonce_used(&quest_id, &3));
+
+    client.complete_quest_with_attestation(&user2, &quest_id, &3, &1100, &signature, &EligibilityProof::None);
+    assert_eq!(token.balance(&user2), 1000);
+
+    client.remove_verifier(&creator, &quest_id);
+    assert_eq!(client.get_verifier(&quest_id), None);
+    client.complete_quest(&Address::generate(&env), &quest_id, &EligibilityProof::None);
+    assert_eq!(client.get_quest(&quest_id).unwrap().current_completions, 3);
 }
 
+#[test]
+fn test_attestation_errors() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let other = Address::generate(&env);
+    let user = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &3000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    let verifier = SigningKey::from_bytes(&[7; 32]);
+    let public_key = BytesN::from_array(&env, &verifier.verifying_key().to_bytes());
+    let quest_id = symbol_short!("quest1");
+    let signature = attest(&env, &verifier, &contract_id, &user, &quest_id, 1, 100);
+
+    assert_eq!(
+        client.try_complete_quest_with_attestation(&user, &quest_id, &1, &100, &signature, &EligibilityProof::None),
+        Err(Ok(Error::QuestNotFound))
+    );
+    create_test_quest(&env, &client, &creator, &quest_id);
+    assert_eq!(
+        client.try_complete_quest_with_attestation(&user, &quest_id, &1, &100, &signature, &EligibilityProof::None),
+        Err(Ok(Error::VerifierNotSet))
+    );
+    assert_eq!(
+        client.try_set_verifier(&other, &quest_id, &public_key),
+        Err(Ok(Error::NotQuestCreator))
+    );
+    assert_eq!(
+        client.try_remove_verifier(&creator, &quest_id),
+        Err(Ok(Error::VerifierNotSet))
+    );
+
+    client.set_verifier(&creator, &quest_id, &public_key);
+    client.complete_quest_with_attestation(&user, &quest_id, &1, &100, &signature, &EligibilityProof::None);
+
+    let signature = attest(&env, &verifier, &contract_id, &user, &quest_id, 2, 100);
+    assert_eq!(
+        client.try_complete_quest_with_attestation(&user, &quest_id, &2, &100, &signature, &EligibilityProof::None),
+        Err(Ok(Error::AlreadyCompleted))
+    );
+
+    client.cancel_quest(&quest_id);
+    let late = Address::generate(&env);
+    let signature = attest(&env, &verifier, &contract_id, &late, &quest_id, 3, 100);
+    assert_eq!(
+        client.try_complete_quest_with_attestation(&late, &quest_id, &3, &100, &signature, &EligibilityProof::None),
+        Err(Ok(Error::QuestNotActive))
+    );
+}
+
+fn create_chained_quest(
+    env: &Env,
+    client: &QuestPlatformClient,
+    creator: &Address,
+    quest_id: &Symbol,
+    prerequisites: Vec<Symbol>,
+) -> Result<(), Error> {
+    match client.try_create_quest(
+        creator,
+        quest_id,
+        &String::from_str(env, "Chained Quest"),
+        &String::from_str(env, "Finish the earlier quests first"),
+        &100,
+        &None,
+        &None,
+        &Some(10),
+        &prerequisites,
+    ) {
+        Ok(_) => {
+            client.activate_quest(creator, quest_id);
+            Ok(())
+        }
+        Err(Ok(error)) => Err(error),
+        Err(Err(_)) => panic!("unexpected host error"),
+    }
+}
+
+#[test]
+fn test_quest_prerequisites_and_chain() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let user = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &100_000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    // intro <- forest, intro <- cave, (forest, cave) <- dragon
+    let intro = symbol_short!("intro");
+    let forest = symbol_short!("forest");
+    let cave = symbol_short!("cave");
+    let dragon = symbol_short!("dragon");
+    create_chained_quest(&env, &client, &creator, &intro, Vec::new(&env)).unwrap();
+    create_chained_quest(&env, &client, &creator, &forest, vec![&env, intro.clone()]).unwrap();
+    create_chained_quest(&env, &client, &creator, &cave, vec![&env, intro.clone()]).unwrap();
+    let dragon_prerequisites = vec![&env, forest.clone(), cave.clone()];
+    create_chained_quest(&env, &client, &creator, &dragon, dragon_prerequisites).unwrap();
+
+    let chain = client.get_quest_chain(&dragon);
+    let nodes: std::vec::Vec<(Symbol, u32)> =
+        chain.iter().map(|node| (node.quest_id, node.depth)).collect();
+    assert_eq!(
+        nodes,
+        [(dragon.clone(), 0), (forest.clone(), 1), (cave.clone(), 1), (intro.clone(), 2)].to_vec()
+    );
+    assert_eq!(chain.get(0).unwrap().prerequisites, vec![&env, forest.clone(), cave.clone()]);
+    assert_eq!(client.get_quest_chain(&intro).len(), 1);
+    assert_eq!(
+        client.try_get_quest_chain(&symbol_short!("missing")),
+        Err(Ok(Error::QuestNotFound))
+    );
+
+    assert_eq!(client.try_complete_quest(&user, &forest, &EligibilityProof::None), Err(Ok(Error::PrerequisiteNotMet)));
+    client.complete_quest(&user, &intro, &EligibilityProof::None);
+    client.complete_quest(&user, &forest, &EligibilityProof::None);
+    assert_eq!(client.try_complete_quest(&user, &dragon, &EligibilityProof::None), Err(Ok(Error::PrerequisiteNotMet)));
+    client.set_review_required(&creator, &dragon, &true);
+    assert_eq!(
+        client.try_submit_completion(
+            &user,
+            &dragon,
+            &BytesN::from_array(&env, &[1; 32]),
+            &String::from_str(&env, "ipfs://proof"),
+            &EligibilityProof::None,
+        ),
+        Err(Ok(Error::PrerequisiteNotMet))
+    );
+    client.set_review_required(&creator, &dragon, &false);
+    client.complete_quest(&user, &cave, &EligibilityProof::None);
+    client.complete_quest(&user, &dragon, &EligibilityProof::None);
+    assert!(client.has_completed(&user, &dragon));
+}
+
+#[test]
+fn test_quest_prerequisite_errors() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &100_000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    let first = symbol_short!("first");
+    assert_eq!(
+        create_chained_quest(&env, &client, &creator, &first, vec![&env, symbol_short!("missing")]),
+        Err(Error::PrerequisiteNotFound)
+    );
+    assert_eq!(
+        create_chained_quest(&env, &client, &creator, &first, vec![&env, first.clone()]),
+        Err(Error::CyclicPrerequisite)
+    );
+    assert_eq!(client.get_quest(&first), None);
+
+    // A straight chain may hold at most 50 quests including the new one
+    let mut previous = quest_symbol(&env, 0);
+    create_chained_quest(&env, &client, &creator, &previous, Vec::new(&env)).unwrap();
+    for i in 1..50u32 {
+        let quest_id = quest_symbol(&env, i);
+        create_chained_quest(&env, &client, &creator, &quest_id, vec![&env, previous]).unwrap();
+        previous = quest_id;
+    }
+    assert_eq!(client.get_quest_chain(&previous).len(), 50);
+    let too_long = quest_symbol(&env, 50);
+    assert_eq!(
+        create_chained_quest(&env, &client, &creator, &too_long, vec![&env, previous]),
+        Err(Error::ChainTooLong)
+    );
+}
+
+#[test]
+fn test_allowlist_and_denylist() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &3000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    // The list is in place before the quest goes live, so outsiders never get a window
+    let quest_id = symbol_short!("quest1");
+    create_draft_quest(&env, &client, &creator, &quest_id);
+    assert_eq!(client.get_quest(&quest_id).unwrap().eligibility, Eligibility::Open);
+    assert!(client.is_eligible(&outsider, &quest_id, &EligibilityProof::None));
+
+    client.set_eligibility(&creator, &quest_id, &Eligibility::Allowlist);
+    assert_eq!(env.auths()[0].0, creator);
+    client.set_listed(&creator, &quest_id, &vec![&env, member.clone()], &true);
+    assert!(client.is_listed(&quest_id, &member));
+    assert_eq!(client.try_complete_quest(&member, &quest_id, &EligibilityProof::None), Err(Ok(Error::QuestNotActive)));
+    client.activate_quest(&creator, &quest_id);
+    assert!(client.is_eligible(&member, &quest_id, &EligibilityProof::None));
+    assert!(!client.is_eligible(&outsider, &quest_id, &EligibilityProof::None));
+    assert_eq!(client.try_complete_quest(&outsider, &quest_id, &EligibilityProof::None), Err(Ok(Error::NotEligible)));
+    client.complete_quest(&member, &quest_id, &EligibilityProof::None);
+
+    // The same list now excludes its members
+    client.set_eligibility(&creator, &quest_id, &Eligibility::Denylist);
+    client.set_listed(&creator, &quest_id, &vec![&env, outsider.clone()], &true);
+    assert!(!client.is_eligible(&outsider, &quest_id, &EligibilityProof::None));
+    assert_eq!(client.try_complete_quest(&outsider, &quest_id, &EligibilityProof::None), Err(Ok(Error::NotEligible)));
+
+    client.set_listed(&creator, &quest_id, &vec![&env, outsider.clone()], &false);
+    assert!(!client.is_listed(&quest_id, &outsider));
+    client.complete_quest(&outsider, &quest_id, &EligibilityProof::None);
+
+    assert_eq!(
+        client.try_set_eligibility(&outsider, &quest_id, &Eligibility::Open),
+        Err(Ok(Error::NotQuestCreator))
+    );
+    assert_eq!(
+        client.try_set_listed(&outsider, &quest_id, &vec![&env, outsider.clone()], &true),
+        Err(Ok(Error::NotQuestCreator))
+    );
+    assert_eq!(
+        client.try_set_eligibility(&creator, &quest_id, &Eligibility::BadgeClass(symbol_short!("slayer"))),
+        Err(Ok(Error::BadgeContractNotSet))
+    );
+    assert_eq!(
+        client.try_is_eligible(&member, &symbol_short!("missing"), &EligibilityProof::None),
+        Err(Ok(Error::QuestNotFound))
+    );
+}
+
+fn strkey(address: &Address) -> std::string::String {
+    use std::string::ToString;
+    soroban_sdk::xdr::ScAddress::from(address).to_string()
+}
+
+fn merkle_proof(env: &Env, tree: &quest_merkle::MerkleTree, user: &Address) -> Vec<BytesN<32>> {
+    let mut proof = Vec::new(env);
+    for sibling in tree.proof(&strkey(user)).unwrap() {
+        proof.push_back(BytesN::from_array(env, &sibling));
+    }
+    proof
+}
+
+#[test]
+fn test_merkle_gated_quest() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &3000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    // Build the tree off-chain from a CSV export
+    let members: std::vec::Vec<Address> = (0..5).map(|_| Address::generate(&env)).collect();
+    let mut csv = std::string::String::from("address\n");
+    for member in &members {
+        csv.push_str(&strkey(member));
+        csv.push('\n');
+    }
+    let tree = quest_merkle::MerkleTree::from_csv(&csv).unwrap();
+
+    let quest_id = symbol_short!("airdrop");
+    create_draft_quest(&env, &client, &creator, &quest_id);
+    let root = BytesN::from_array(&env, &tree.root());
+    client.set_eligibility(&creator, &quest_id, &Eligibility::MerkleRoot(root));
+    client.activate_quest(&creator, &quest_id);
+
+    let proof = EligibilityProof::Merkle(merkle_proof(&env, &tree, &members[0]));
+    assert!(client.is_eligible(&members[0], &quest_id, &proof));
+    assert!(!client.is_eligible(&members[0], &quest_id, &EligibilityProof::None));
+    assert!(!client.is_eligible(&outsider, &quest_id, &proof));
+    assert_eq!(
+        client.try_complete_quest(&outsider, &quest_id, &proof),
+        Err(Ok(Error::NotEligible))
+    );
+
+    let mut tampered = merkle_proof(&env, &tree, &members[1]);
+    tampered.set(0, BytesN::from_array(&env, &[0; 32]));
+    assert_eq!(
+        client.try_complete_quest(&members[1], &quest_id, &EligibilityProof::Merkle(tampered)),
+        Err(Ok(Error::NotEligible))
+    );
+
+    client.complete_quest(&members[0], &quest_id, &proof);
+    assert_eq!(token.balance(&members[0]), 1000);
+
+    // Reviewed quests take the proof on submission
+    client.set_review_required(&creator, &quest_id, &true);
+    client.submit_completion(
+        &members[1],
+        &quest_id,
+        &BytesN::from_array(&env, &[1; 32]),
+        &String::from_str(&env, "ipfs://proof"),
+        &EligibilityProof::Merkle(merkle_proof(&env, &tree, &members[1])),
+    );
+    client.approve_submission(&creator, &members[1], &quest_id);
+    assert_eq!(token.balance(&members[1]), 1000);
+    client.set_review_required(&creator, &quest_id, &false);
+
+    // Verified quests take it alongside the attestation
+    let verifier = SigningKey::from_bytes(&[7; 32]);
+    let public_key = BytesN::from_array(&env, &verifier.verifying_key().to_bytes());
+    client.set_verifier(&creator, &quest_id, &public_key);
+    let signature = attest(&env, &verifier, &contract_id, &members[2], &quest_id, 1, 100);
+    assert_eq!(
+        client.try_complete_quest_with_attestation(&members[2], &quest_id, &1, &100, &signature, &EligibilityProof::None),
+        Err(Ok(Error::NotEligible))
+    );
+    let proof = EligibilityProof::Merkle(merkle_proof(&env, &tree, &members[2]));
+    client.complete_quest_with_attestation(&members[2], &quest_id, &1, &100, &signature, &proof);
+    assert_eq!(token.balance(&members[2]), 1000);
+}
+
+#[test]
+fn test_admin_errors() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+
+    assert_eq!(
+        client.try_cancel_quest(&symbol_short!("quest1")),
+        Err(Ok(Error::NotInitialized))
+    );
+    assert_eq!(client.try_set_leaderboard_size(&5), Err(Ok(Error::NotInitialized)));
+
+    client.initialize(&admin, &Address::generate(&env));
+    assert_eq!(client.try_set_leaderboard_size(&0), Err(Ok(Error::InvalidLeaderboardSize)));
+    assert_eq!(client.try_set_leaderboard_size(&101), Err(Ok(Error::InvalidLeaderboardSize)));
+}
+
+fn quest_ids(quests: &Vec<Quest>) -> std::vec::Vec<Symbol> {
+    quests.iter().map(|quest| quest.id).collect()
+}
+
+#[test]
+fn test_get_active_quests_paginated() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &100_000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    let ids: std::vec::Vec<Symbol> = (0..5u32).map(|i| quest_symbol(&env, i)).collect();
+    for quest_id in ids.iter() {
+        create_test_quest(&env, &client, &creator, quest_id);
+    }
+
+    assert_eq!(client.get_status_count(&symbol_short!("active")), 5);
+    assert_eq!(quest_ids(&client.get_active_quests(&0, &10)), ids);
+    assert_eq!(quest_ids(&client.get_active_quests(&1, &2)), ids[1..3].to_vec());
+    assert_eq!(quest_ids(&client.get_active_quests(&4, &10)), ids[4..].to_vec());
+    assert_eq!(client.get_active_quests(&5, &10).len(), 0);
+    assert_eq!(client.get_active_quests(&u32::MAX, &u32::MAX).len(), 0);
+
+    // Expired quests drop out of the page but keep their slot in the index
+    let timed = symbol_short!("timed");
+    client.create_quest(
+        &creator,
+        &timed,
+        &String::from_str(&env, "Timed Quest"),
+        &String::from_str(&env, "Finish before the deadline"),
+        &1000,
+        &None,
+        &Some(100),
+        &Some(3),
+        &Vec::new(&env),
+    );
+    client.activate_quest(&creator, &timed);
+    assert_eq!(client.get_active_quests(&5, &10).len(), 1);
+    env.ledger().with_mut(|li| li.timestamp = 101);
+    assert_eq!(client.get_active_quests(&5, &10).len(), 0);
+    assert_eq!(quest_ids(&client.get_active_quests(&0, &10)), ids);
+    assert_eq!(client.get_status_count(&symbol_short!("active")), 6);
+    assert_eq!(client.get_quests_by_status(&symbol_short!("active"), &5, &10).get(0).unwrap().id, timed);
+}
+
+#[test]
+fn test_status_index_follows_transitions() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let user = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &100_000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    let ids: std::vec::Vec<Symbol> = (0..4u32).map(|i| quest_symbol(&env, i)).collect();
+    for quest_id in ids.iter() {
+        create_test_quest(&env, &client, &creator, quest_id);
+    }
+    let solo = symbol_short!("solo");
+    client.create_quest(
+        &creator,
+        &solo,
+        &String::from_str(&env, "Solo Quest"),
+        &String::from_str(&env, "Only one player can finish this"),
+        &1000,
+        &None,
+        &None,
+        &Some(1),
+        &Vec::new(&env),
+    );
+    client.activate_quest(&creator, &solo);
+
+    // Filling the last slot moves the quest to the completed index
+    client.complete_quest(&user, &solo, &EligibilityProof::None);
+    assert_eq!(quest_ids(&client.get_active_quests(&0, &10)), ids);
+    let completed = client.get_quests_by_status(&symbol_short!("completed"), &0, &10);
+    assert_eq!(quest_ids(&completed), [solo.clone()].to_vec());
+    assert_eq!(completed.get(0).unwrap().status, symbol_short!("completed"));
+
+    // Cancelling fills the gap with the last active quest
+    client.cancel_quest(&ids[1]);
+    assert_eq!(
+        quest_ids(&client.get_active_quests(&0, &10)),
+        [ids[0].clone(), ids[3].clone(), ids[2].clone()].to_vec()
+    );
+    let cancelled = client.get_quests_by_status(&symbol_short!("cancelled"), &0, &10);
+    assert_eq!(quest_ids(&cancelled), [ids[1].clone()].to_vec());
+
+    client.cancel_quest(&ids[3]);
+    client.cancel_quest(&ids[2]);
+    client.cancel_quest(&ids[0]);
+    assert_eq!(client.get_status_count(&symbol_short!("active")), 0);
+    assert_eq!(client.get_status_count(&symbol_short!("completed")), 1);
+    assert_eq!(client.get_status_count(&symbol_short!("cancelled")), 4);
+    assert_eq!(client.get_active_quests(&0, &10).len(), 0);
+}
+
+#[test]
+fn test_get_active_quests_page_size_is_capped() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &1_000_000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    for i in 0..60u32 {
+        create_test_quest(&env, &client, &creator, &quest_symbol(&env, i));
+    }
+
+    assert_eq!(client.get_active_quests(&0, &100).len(), 50);
+    assert_eq!(client.get_active_quests(&50, &100).len(), 10);
+}
+
+#[test]
+fn test_user_completion_history_and_stats() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &100_000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    let stats = client.get_user_stats(&user);
+    assert_eq!(stats.total_completions, 0);
+    assert_eq!(stats.total_rewards, 0);
+    assert_eq!(client.get_user_completions(&user, &0, &10).len(), 0);
+
+    for i in 0..3u32 {
+        client.create_quest(
+            &creator,
+            &quest_symbol(&env, i),
+            &String::from_str(&env, "Test Quest"),
+            &String::from_str(&env, "Complete this test quest"),
+            &(100 * (i as i128 + 1)),
+            &None,
+            &None,
+            &Some(2),
+            &Vec::new(&env),
+        );
+        client.activate_quest(&creator, &quest_symbol(&env, i));
+    }
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    client.complete_quest(&user, &quest_symbol(&env, 2), &EligibilityProof::None);
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+    client.complete_quest(&user, &quest_symbol(&env, 0), &EligibilityProof::None);
+    client.complete_quest(&other, &quest_symbol(&env, 1), &EligibilityProof::None);
+    env.ledger().with_mut(|li| li.timestamp = 3000);
+    client.complete_quest(&user, &quest_symbol(&env, 1), &EligibilityProof::None);
+
+    let history = client.get_user_completions(&user, &0, &10);
+    assert_eq!(history.len(), 3);
+    let first = history.get(0).unwrap();
+    assert_eq!(first.quest_id, quest_symbol(&env, 2));
+    assert_eq!(first.completed_at, 1000);
+    assert_eq!(first.reward_amount, 300);
+    assert_eq!(history.get(1).unwrap().quest_id, quest_symbol(&env, 0));
+    assert_eq!(history.get(2).unwrap().completed_at, 3000);
+
+    let page = client.get_user_completions(&user, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().quest_id, quest_symbol(&env, 0));
+
+    let stats = client.get_user_stats(&user);
+    assert_eq!(stats.total_completions, 3);
+    assert_eq!(stats.total_rewards, 600);
+    assert_eq!(token.balance(&user), 600);
+
+    let other_stats = client.get_user_stats(&other);
+    assert_eq!(other_stats.total_completions, 1);
+    assert_eq!(other_stats.total_rewards, 200);
+}
+
+fn create_open_quest(
+    env: &Env,
+    client: &QuestPlatformClient,
+    creator: &Address,
+    quest_id: &Symbol,
+    reward_amount: i128,
+) {
+    client.create_quest(
+        creator,
+        quest_id,
+        &String::from_str(env, "Open Quest"),
+        &String::from_str(env, "Anyone can complete this quest"),
+        &reward_amount,
+        &None,
+        &None,
+        &Some(100),
+        &Vec::new(env),
+    );
+    client.activate_quest(creator, quest_id);
+}
+
+fn leaderboard_users(board: &Vec<LeaderboardEntry>) -> std::vec::Vec<Address> {
+    board.iter().map(|entry| entry.user).collect()
+}
+
+#[test]
+fn test_leaderboard_ranks_by_completions_then_rewards() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &1_000_000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    let cheap = symbol_short!("cheap");
+    let rich = symbol_short!("rich");
+    let extra = symbol_short!("extra");
+    create_open_quest(&env, &client, &creator, &cheap, 10);
+    create_open_quest(&env, &client, &creator, &rich, 50);
+    create_open_quest(&env, &client, &creator, &extra, 10);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let dave = Address::generate(&env);
+
+    client.complete_quest(&alice, &cheap, &EligibilityProof::None);
+    client.complete_quest(&bob, &rich, &EligibilityProof::None);
+    // Same completions, bob earned more
+    assert_eq!(leaderboard_users(&client.get_leaderboard()), [bob.clone(), alice.clone()].to_vec());
+
+    // Exact tie with alice keeps the earlier achiever ahead
+    client.complete_quest(&carol, &cheap, &EligibilityProof::None);
+    assert_eq!(
+        leaderboard_users(&client.get_leaderboard()),
+        [bob.clone(), alice.clone(), carol.clone()].to_vec()
+    );
+
+    // More completions beat more rewards
+    client.complete_quest(&carol, &extra, &EligibilityProof::None);
+    assert_eq!(
+        leaderboard_users(&client.get_leaderboard()),
+        [carol.clone(), bob.clone(), alice.clone()].to_vec()
+    );
+
+    let top = client.get_leaderboard().get(0).unwrap();
+    assert_eq!(top.completions, 2);
+    assert_eq!(top.rewards, 20);
+    assert_eq!(client.get_rank(&carol), Some(1));
+    assert_eq!(client.get_rank(&alice), Some(3));
+    assert_eq!(client.get_rank(&dave), None);
+}
+
+#[test]
+fn test_leaderboard_evicts_beyond_top_n() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &1_000_000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+    assert_eq!(client.get_leaderboard_size(), 10);
+    client.set_leaderboard_size(&2);
+
+    let first = symbol_short!("first");
+    let second = symbol_short!("second");
+    create_open_quest(&env, &client, &creator, &first, 10);
+    create_open_quest(&env, &client, &creator, &second, 10);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    client.complete_quest(&alice, &first, &EligibilityProof::None);
+    client.complete_quest(&bob, &first, &EligibilityProof::None);
+    // A tie with the last slot does not evict the incumbent
+    client.complete_quest(&carol, &first, &EligibilityProof::None);
+    assert_eq!(leaderboard_users(&client.get_leaderboard()), [alice.clone(), bob.clone()].to_vec());
+    assert_eq!(client.get_rank(&carol), None);
+
+    // Overtaking pushes the last entry out
+    client.complete_quest(&carol, &second, &EligibilityProof::None);
+    assert_eq!(leaderboard_users(&client.get_leaderboard()), [carol.clone(), alice.clone()].to_vec());
+    assert_eq!(client.get_rank(&bob), None);
+
+    // An evicted user re-enters once they climb back
+    client.complete_quest(&bob, &second, &EligibilityProof::None);
+    assert_eq!(leaderboard_users(&client.get_leaderboard()), [carol.clone(), bob.clone()].to_vec());
+
+    client.set_leaderboard_size(&1);
+    assert_eq!(leaderboard_users(&client.get_leaderboard()), [carol.clone()].to_vec());
+}
+
+#[test]
+fn test_leaderboard_with_many_users() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &10_000_000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+    client.set_leaderboard_size(&5);
+
+    let quests: std::vec::Vec<Symbol> = (0..6u32).map(|i| quest_symbol(&env, i)).collect();
+    for (i, quest_id) in quests.iter().enumerate() {
+        create_open_quest(&env, &client, &creator, quest_id, 10 * (i as i128 + 1));
+    }
+
+    // User n completes the first (n % 6) quests, interleaved across users
+    let users: std::vec::Vec<Address> = (0..20).map(|_| Address::generate(&env)).collect();
+    for (q, quest_id) in quests.iter().enumerate() {
+        for (n, user) in users.iter().enumerate() {
+            if q < n % 6 {
+                client.complete_quest(user, quest_id, &EligibilityProof::None);
+            }
+        }
+    }
+
+    let mut expected: std::vec::Vec<(u32, i128, usize)> = users
+        .iter()
+        .enumerate()
+        .map(|(n, _)| {
+            let done = (n % 6) as u32;
+            let rewards = (1..=done as i128).map(|k| 10 * k).sum();
+            (done, rewards, n)
+        })
+        .collect();
+    // Equal scores keep the user who reached it first, i.e. the lower index
+    expected.sort_by(|a, b| b.0.cmp(&a.0).then(b.1.cmp(&a.1)).then(a.2.cmp(&b.2)));
+
+    let board = client.get_leaderboard();
+    assert_eq!(board.len(), 5);
+    for (rank, entry) in board.iter().enumerate() {
+        let (done, rewards, n) = expected[rank];
+        assert_eq!(entry.user, users[n]);
+        assert_eq!(entry.completions, done);
+        assert_eq!(entry.rewards, rewards);
+        assert_eq!(client.get_rank(&users[n]), Some(rank as u32 + 1));
+    }
+    assert_eq!(client.get_rank(&users[0]), None);
+}
+
+fn create_badge_class(env: &Env, badges: &badge_nft::BadgeNFTClient, issuer: &Address, class_id: &Symbol) {
+    badges.create_class(
+        issuer,
+        class_id,
+        &badge_nft::BadgeMetadata {
+            name: String::from_str(env, "Dragon Slayer"),
+            description: String::from_str(env, "Defeat the dragon"),
+            image_uri: String::from_str(env, "ipfs://dragon-slayer"),
+            rarity: badge_nft::Rarity::Rare,
+            attributes: Map::new(env),
+        },
+        &None,
+        &false,
+        &None,
+    );
+}
+
+fn create_badge_quest(
+    env: &Env,
+    client: &QuestPlatformClient,
+    creator: &Address,
+    quest_id: &Symbol,
+    badge_id: &Symbol,
+) {
+    client.create_quest(
+        creator,
+        quest_id,
+        &String::from_str(env, "Dragon Slayer"),
+        &String::from_str(env, "Defeat the dragon"),
+        &1000,
+        &Some(badge_id.clone()),
+        &None,
+        &Some(3),
+        &Vec::new(env),
+    );
+    client.activate_quest(creator, quest_id);
+}
+
+#[test]
+fn test_complete_quest_mints_badge() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &100_000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    let badge_contract = env.register_contract(None, badge_nft::BadgeNFT);
+    let badges = badge_nft::BadgeNFTClient::new(&env, &badge_contract);
+    badges.initialize(&admin, &contract_id);
+    client.set_badge_contract(&badge_contract);
+    assert_eq!(client.get_badge_contract(), Some(badge_contract.clone()));
+
+    let quest_id = symbol_short!("dragon");
+    let class_id = symbol_short!("slayer");
+    create_badge_class(&env, &badges, &creator, &class_id);
+    create_badge_quest(&env, &client, &creator, &quest_id, &class_id);
+    create_test_quest(&env, &client, &creator, &symbol_short!("plain"));
+
+    client.complete_quest(&user1, &quest_id, &EligibilityProof::None);
+    client.complete_quest(&user2, &quest_id, &EligibilityProof::None);
+    client.complete_quest(&user1, &symbol_short!("plain"), &EligibilityProof::None);
+
+    let user1_badges = badges.badges_of(&user1, &0, &50);
+    let user2_badges = badges.badges_of(&user2, &0, &50);
+    assert_eq!(user1_badges.len(), 1);
+    assert_eq!(user2_badges.len(), 1);
+    assert_ne!(user1_badges.get(0), user2_badges.get(0));
+    assert_eq!(badges.total_badges(), 2);
+
+    let badge = badges.get_badge(&user1_badges.get(0).unwrap()).unwrap();
+    assert_eq!(badge.owner, user1);
+    assert_eq!(badge.class_id, class_id);
+    assert_eq!(badge.quest_id, Some(quest_id));
+    assert_eq!(badges.class_supply(&class_id), 2);
+
+    let history = client.get_user_completions(&user1, &0, &10);
+    assert_eq!(history.get(0).unwrap().badge_token, user1_badges.get(0));
+    assert_eq!(history.get(1).unwrap().badge_token, None);
+}
+
+#[test]
+fn test_badge_quest_requires_badge_contract() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &100_000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    let result = client.try_create_quest(
+        &creator,
+        &symbol_short!("dragon"),
+        &String::from_str(&env, "Dragon Slayer"),
+        &String::from_str(&env, "Defeat the dragon"),
+        &1000,
+        &Some(symbol_short!("slayer")),
+        &None,
+        &Some(3),
+        &Vec::new(&env),
+    );
+    assert_eq!(result, Err(Ok(Error::BadgeContractNotSet)));
+}
+
+#[test]
+fn test_badge_quest_requires_own_class() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let other_creator = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &100_000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    let badge_contract = env.register_contract(None, badge_nft::BadgeNFT);
+    let badges = badge_nft::BadgeNFTClient::new(&env, &badge_contract);
+    badges.initialize(&admin, &contract_id);
+    client.set_badge_contract(&badge_contract);
+
+    let class_id = symbol_short!("slayer");
+    create_badge_class(&env, &badges, &other_creator, &class_id);
+
+    for badge_id in [class_id, symbol_short!("missing")] {
+        let result = client.try_create_quest(
+            &creator,
+            &symbol_short!("dragon"),
+            &String::from_str(&env, "Dragon Slayer"),
+            &String::from_str(&env, "Defeat the dragon"),
+            &1000,
+            &Some(badge_id),
+            &None,
+            &Some(3),
+            &Vec::new(&env),
+        );
+        assert_eq!(result, Err(Ok(Error::BadgeClassNotOwned)));
+    }
+}
+
+#[test]
+fn test_badge_gated_quests() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let holder = Address::generate(&env);
+    let newcomer = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &100_000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    let badge_contract = env.register_contract(None, badge_nft::BadgeNFT);
+    let badges = badge_nft::BadgeNFTClient::new(&env, &badge_contract);
+    badges.initialize(&admin, &contract_id);
+    client.set_badge_contract(&badge_contract);
+
+    let dragon = symbol_short!("dragon");
+    let class_id = symbol_short!("slayer");
+    create_badge_class(&env, &badges, &creator, &class_id);
+    create_badge_quest(&env, &client, &creator, &dragon, &class_id);
+
+    let by_class = symbol_short!("by_class");
+    let by_quest = symbol_short!("by_quest");
+    create_test_quest(&env, &client, &creator, &by_class);
+    create_test_quest(&env, &client, &creator, &by_quest);
+    client.set_eligibility(&creator, &by_class, &Eligibility::BadgeClass(class_id.clone()));
+    client.set_eligibility(&creator, &by_quest, &Eligibility::QuestBadge(dragon.clone()));
+    assert_eq!(
+        client.try_set_eligibility(&creator, &by_quest, &Eligibility::QuestBadge(symbol_short!("missing"))),
+        Err(Ok(Error::QuestNotFound))
+    );
+    assert_eq!(
+        client.try_set_eligibility(&creator, &by_quest, &Eligibility::QuestBadge(by_class.clone())),
+        Err(Ok(Error::QuestAwardsNoBadge))
+    );
+
+    assert!(!client.is_eligible(&holder, &by_class, &EligibilityProof::None));
+    assert!(!client.is_eligible(&holder, &by_quest, &EligibilityProof::Badge(0)));
+    assert_eq!(client.try_complete_quest(&holder, &by_class, &EligibilityProof::None), Err(Ok(Error::NotEligible)));
+
+    client.complete_quest(&holder, &dragon, &EligibilityProof::None);
+    let badge = EligibilityProof::Badge(badges.badges_of(&holder, &0, &50).get(0).unwrap());
+    assert!(client.is_eligible(&holder, &by_class, &badge));
+    assert!(client.is_eligible(&holder, &by_quest, &badge));
+    assert!(!client.is_eligible(&holder, &by_class, &EligibilityProof::None));
+    assert!(!client.is_eligible(&newcomer, &by_class, &badge));
+    client.complete_quest(&holder, &by_class, &badge);
+
+    // A badge tagged with the quest but of another class does not count
+    let fake = symbol_short!("fake");
+    create_badge_class(&env, &badges, &newcomer, &fake);
+    let fake_badge = badges.mint_badge(&contract_id, &newcomer, &fake, &Some(dragon.clone()));
+    assert!(!client.is_eligible(&newcomer, &by_quest, &EligibilityProof::Badge(fake_badge)));
+
+    // A rented badge opens the gate for the renter, not the owner, until the rental ends
+    let badge_id = badges.badges_of(&holder, &0, &50).get(0).unwrap();
+    badges.set_user(&holder, &badge_id, &newcomer, &1000);
+    assert!(client.is_eligible(&newcomer, &by_quest, &badge));
+    assert!(!client.is_eligible(&holder, &by_quest, &badge));
+    env.ledger().with_mut(|li| li.timestamp = 1001);
+    assert!(!client.is_eligible(&newcomer, &by_quest, &badge));
+    assert!(client.is_eligible(&holder, &by_quest, &badge));
+
+    // Revoked badges no longer open the gate
+    badges.revoke_badge(&creator, &badge_id, &1);
+    assert!(!client.is_eligible(&holder, &by_quest, &badge));
+    assert_eq!(client.try_complete_quest(&holder, &by_quest, &badge), Err(Ok(Error::NotEligible)));
+}
+
+#[test]
+fn test_self_created_quests_do_not_count_for_rules() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let farmer = Address::generate(&env);
+    let player = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &100_000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    let badge_contract = env.register_contract(None, badge_nft::BadgeNFT);
+    let badges = badge_nft::BadgeNFTClient::new(&env, &badge_contract);
+    badges.initialize(&admin, &contract_id);
+    client.set_badge_contract(&badge_contract);
+
+    let veteran = symbol_short!("veteran");
+    let rule_id = symbol_short!("quests2");
+    create_badge_class(&env, &badges, &admin, &veteran);
+    badges.create_rule(&rule_id, &badge_nft::RuleCondition::DistinctQuests(2), &veteran);
+
+    // Anyone can issue a class and complete their own free single-use quests
+    let farm = symbol_short!("farm");
+    create_badge_class(&env, &badges, &farmer, &farm);
+    assert!(!badges.get_class(&farm).unwrap().trusted);
+    for quest_id in [symbol_short!("free1"), symbol_short!("free2")] {
+        client.create_quest(
+            &farmer,
+            &quest_id,
+            &String::from_str(&env, "Free Badge"),
+            &String::from_str(&env, "Nothing to do"),
+            &0,
+            &Some(farm.clone()),
+            &None,
+            &Some(1),
+            &Vec::new(&env),
+        );
+        client.activate_quest(&farmer, &quest_id);
+        client.complete_quest(&farmer, &quest_id, &EligibilityProof::None);
+    }
+    assert_eq!(badges.balance_of(&farmer), 2);
+    assert!(!badges.has_achievement(&farmer, &rule_id));
+
+    // Quests awarding a class of an approved minter do count
+    badges.add_minter(&creator);
+    let slayer = symbol_short!("slayer");
+    create_badge_class(&env, &badges, &creator, &slayer);
+    assert!(badges.get_class(&slayer).unwrap().trusted);
+    create_badge_quest(&env, &client, &creator, &symbol_short!("dragon"), &slayer);
+    create_badge_quest(&env, &client, &creator, &symbol_short!("hydra"), &slayer);
+    client.complete_quest(&player, &symbol_short!("dragon"), &EligibilityProof::None);
+    assert!(!badges.has_achievement(&player, &rule_id));
+    client.complete_quest(&player, &symbol_short!("hydra"), &EligibilityProof::None);
+    assert!(badges.has_achievement(&player, &rule_id));
+}
+
+#[test]
+fn test_badge_mint_requires_registered_platform() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let user = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &100_000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    // BadgeNFT trusts some other platform, so the cross-contract mint is rejected
+    let badge_contract = env.register_contract(None, badge_nft::BadgeNFT);
+    let badges = badge_nft::BadgeNFTClient::new(&env, &badge_contract);
+    badges.initialize(&admin, &Address::generate(&env));
+    client.set_badge_contract(&badge_contract);
+
+    let quest_id = symbol_short!("dragon");
+    let class_id = symbol_short!("slayer");
+    create_badge_class(&env, &badges, &creator, &class_id);
+    create_badge_quest(&env, &client, &creator, &quest_id, &class_id);
+    assert!(client.try_complete_quest(&user, &quest_id, &EligibilityProof::None).is_err());
+    assert!(!client.has_completed(&user, &quest_id));
+    assert_eq!(token.balance(&user), 0);
+
+    // Approving the platform as a minter is not enough to tag badges with a quest
+    badges.add_minter(&contract_id);
+    assert!(client.try_complete_quest(&user, &quest_id, &EligibilityProof::None).is_err());
+
+    // A BadgeNFT that trusts this platform fixes it
+    let badge_contract = env.register_contract(None, badge_nft::BadgeNFT);
+    let badges = badge_nft::BadgeNFTClient::new(&env, &badge_contract);
+    badges.initialize(&admin, &contract_id);
+    client.set_badge_contract(&badge_contract);
+    create_badge_class(&env, &badges, &creator, &class_id);
+    client.complete_quest(&user, &quest_id, &EligibilityProof::None);
+    assert_eq!(badges.badges_of(&user, &0, &50).len(), 1);
+}
+
+/// Runs one create + complete round trip in a fresh `Env` loaded from the
+/// current ledger state, so only the entries touched by the calls are metered
+fn measure_quest_round_trip(
+    env: &Env,
+    contract_id: &Address,
+    creator: &Address,
+    user: &Address,
+    quest_id: &str,
+) -> (u64, u64) {
+    let fresh = Env::from_snapshot(env.to_snapshot());
+    fresh.mock_all_auths();
+    let import = |address: &Address| -> Address {
+        let sc_address = soroban_sdk::xdr::ScAddress::from(address);
+        Address::try_from_val(&fresh, &sc_address).unwrap()
+    };
+    let contract_id = import(contract_id);
+    let creator = import(creator);
+    let user = import(user);
+    fresh.register_contract(&contract_id, QuestPlatform);
+    let client = QuestPlatformClient::new(&fresh, &contract_id);
+    let quest_id = Symbol::new(&fresh, quest_id);
+
+    fresh.budget().reset_default();
+    create_test_quest(&fresh, &client, &creator, &quest_id);
+    let create_cost = fresh.budget().cpu_instruction_cost();
+
+    fresh.budget().reset_default();
+    client.complete_quest(&user, &quest_id, &EligibilityProof::None);
+    let complete_cost = fresh.budget().cpu_instruction_cost();
+
+    (create_cost, complete_cost)
+}
+
+#[test]
+fn test_storage_cost_stays_flat_with_many_quests() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let user = Address::generate(&env);
+    let (token, token_admin) = create_reward_token(&env, &admin);
+    token_admin.mint(&creator, &100_000_000);
+
+    let contract_id = env.register_contract(None, QuestPlatform);
+    let client = QuestPlatformClient::new(&env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    for i in 0..10u32 {
+        create_test_quest(&env, &client, &creator, &quest_symbol(&env, i));
+    }
+    let (base_create, base_complete) =
+        measure_quest_round_trip(&env, &contract_id, &creator, &user, "probe");
+
+    // Fill the ledger with thousands of quests
+    env.as_contract(&contract_id, || {
+        for i in 10..2000u32 {
+            write_quest(&env, &Quest {
+                id: quest_symbol(&env, i),
+                creator: creator.clone(),
+                title: String::from_str(&env, "Test Quest"),
+                description: String::from_str(&env, "Complete this test quest"),
+                reward_amount: 1000,
+                reward_token: token.address.clone(),
+                badge_id: None,
+                status: symbol_short!("active"),
+                created_at: 0,
+                expires_at: None,
+                max_completions: Some(3),
+                current_completions: 0,
+                review_required: false,
+                prerequisites: Vec::new(&env),
+                eligibility: Eligibility::Open,
+            });
+        }
+    });
+    let (later_create, later_complete) =
+        measure_quest_round_trip(&env, &contract_id, &creator, &user, "probe");
+
+    assert_eq!(later_create, base_create);
+    assert_eq!(later_complete, base_complete);
+}
+
+fn quest_symbol(env: &Env, i: u32) -> Symbol {
+    let mut name = [b'q'; 6];
+    let mut n = i;
+    for byte in name.iter_mut().skip(1).rev() {
+        *byte = b'0' + (n % 10) as u8;
+        n /= 10;
+    }
+    Symbol::new(env, core::str::from_utf8(&name).unwrap())
+}
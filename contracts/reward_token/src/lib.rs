@@ -1,15 +1,179 @@
 //! Reward Token Contract
 //! Stellar Adventure Quest Platform - Reward Token (Mintable)
 //! Token used for quest rewards, can be minted by authorized addresses
+//! Implements the standard Soroban token interface (SEP-41)
 
 #![no_std]
 use soroban_sdk::{
-    contract, contractimpl, symbol_short,
+    contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short,
+    token::{self, Interface as _},
     Address, Env, String,
 };
 
-// Use the standard Stellar Asset Contract as a base
-// For simplicity, we'll create a mintable token wrapper
+const DAY_IN_LEDGERS: u32 = 17280;
+const INSTANCE_BUMP_AMOUNT: u32 = 7 * DAY_IN_LEDGERS;
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+const BALANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const BALANCE_LIFETIME_THRESHOLD: u32 = BALANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+/// Quest rewards use the same precision as native Stellar assets
+const DECIMALS: u32 = 7;
+
+/// Contract errors, codes are stable and surfaced to clients
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    NotInitialized = 1,
+    NegativeAmount = 2,
+    InsufficientBalance = 3,
+    InsufficientAllowance = 4,
+    ExpirationInPast = 5,
+    Overflow = 6,
+    AlreadyInitialized = 7,
+    NoPendingAdmin = 8,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowanceDataKey {
+    pub from: Address,
+    pub spender: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowanceValue {
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Balance(Address),
+    Allowance(AllowanceDataKey),
+}
+
+fn read_admin(env: &Env) -> Result<Address, Error> {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("admin"))
+        .ok_or(Error::NotInitialized)
+}
+
+fn check_nonnegative_amount(env: &Env, amount: i128) {
+    if amount < 0 {
+        panic_with_error!(env, Error::NegativeAmount);
+    }
+}
+
+fn bump_instance(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+fn read_balance(env: &Env, id: &Address) -> i128 {
+    let key = DataKey::Balance(id.clone());
+    if let Some(balance) = env.storage().persistent().get::<DataKey, i128>(&key) {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+        balance
+    } else {
+        0
+    }
+}
+
+fn write_balance(env: &Env, id: &Address, amount: i128) {
+    let key = DataKey::Balance(id.clone());
+    env.storage().persistent().set(&key, &amount);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+fn receive_balance(env: &Env, id: &Address, amount: i128) {
+    let balance = read_balance(env, id);
+    match balance.checked_add(amount) {
+        Some(balance) => write_balance(env, id, balance),
+        None => panic_with_error!(env, Error::Overflow),
+    }
+}
+
+fn spend_balance(env: &Env, id: &Address, amount: i128) {
+    let balance = read_balance(env, id);
+    if balance < amount {
+        panic_with_error!(env, Error::InsufficientBalance);
+    }
+    write_balance(env, id, balance - amount);
+}
+
+fn reduce_supply(env: &Env, amount: i128) {
+    let supply: i128 = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("supply"))
+        .unwrap_or(0);
+    env.storage().instance().set(&symbol_short!("supply"), &(supply - amount));
+}
+
+fn read_allowance(env: &Env, from: &Address, spender: &Address) -> AllowanceValue {
+    let key = DataKey::Allowance(AllowanceDataKey {
+        from: from.clone(),
+        spender: spender.clone(),
+    });
+    match env.storage().temporary().get::<DataKey, AllowanceValue>(&key) {
+        Some(allowance) if allowance.expiration_ledger >= env.ledger().sequence() => allowance,
+        Some(allowance) => AllowanceValue {
+            amount: 0,
+            expiration_ledger: allowance.expiration_ledger,
+        },
+        None => AllowanceValue {
+            amount: 0,
+            expiration_ledger: 0,
+        },
+    }
+}
+
+fn write_allowance(env: &Env, from: &Address, spender: &Address, amount: i128, expiration_ledger: u32) {
+    if amount > 0 && expiration_ledger < env.ledger().sequence() {
+        panic_with_error!(env, Error::ExpirationInPast);
+    }
+
+    let key = DataKey::Allowance(AllowanceDataKey {
+        from: from.clone(),
+        spender: spender.clone(),
+    });
+    let allowance = AllowanceValue {
+        amount,
+        expiration_ledger,
+    };
+    env.storage().temporary().set(&key, &allowance);
+
+    // Keep the entry alive exactly as long as the allowance is usable
+    if amount > 0 {
+        let live_for = expiration_ledger - env.ledger().sequence();
+        env.storage().temporary().extend_ttl(&key, live_for, live_for);
+    }
+}
+
+fn spend_allowance(env: &Env, from: &Address, spender: &Address, amount: i128) {
+    let allowance = read_allowance(env, from, spender);
+    if allowance.amount < amount {
+        panic_with_error!(env, Error::InsufficientAllowance);
+    }
+    if amount > 0 {
+        write_allowance(
+            env,
+            from,
+            spender,
+            allowance.amount - amount,
+            allowance.expiration_ledger,
+        );
+    }
+}
 
 #[contract]
 pub struct RewardToken;
@@ -17,50 +181,226 @@ pub struct RewardToken;
 #[contractimpl]
 impl RewardToken {
     /// Initialize the reward token
-    /// This is a simple wrapper around Stellar Asset Contract
-    /// For production, integrate with Soroban Token Contract or Stellar Asset Contract
-    pub fn initialize(env: Env, admin: Address, name: String, symbol: String) {
+    pub fn initialize(env: Env, admin: Address, name: String, symbol: String) -> Result<(), Error> {
+        if env.storage().instance().has(&symbol_short!("init")) {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+
         env.storage().instance().set(&symbol_short!("admin"), &admin);
         env.storage().instance().set(&symbol_short!("name"), &name);
         env.storage().instance().set(&symbol_short!("symbol"), &symbol);
+        env.storage().instance().set(&symbol_short!("supply"), &0i128);
         env.storage().instance().set(&symbol_short!("init"), &true);
+        bump_instance(&env);
+
+        Ok(())
+    }
+
+    /// Mint tokens to an address (admin only)
+    pub fn mint(env: Env, to: Address, amount: i128) -> Result<(), Error> {
+        if amount < 0 {
+            return Err(Error::NegativeAmount);
+        }
+        let admin = read_admin(&env)?;
+        admin.require_auth();
+
+        bump_instance(&env);
+
+        let supply: i128 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("supply"))
+            .unwrap_or(0);
+        let supply = supply.checked_add(amount).ok_or(Error::Overflow)?;
+        env.storage().instance().set(&symbol_short!("supply"), &supply);
+        receive_balance(&env, &to, amount);
+
+        env.events().publish(
+            (symbol_short!("mint"), admin, to),
+            amount,
+        );
+
+        Ok(())
+    }
+
+    /// Get total amount of tokens in circulation
+    pub fn total_supply(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("supply"))
+            .unwrap_or(0)
+    }
+
+    /// Admin: Propose a new admin, who must accept before taking over
+    pub fn propose_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        let admin = read_admin(&env)?;
+        admin.require_auth();
+
+        env.storage().instance().set(&symbol_short!("pend_adm"), &new_admin);
+
+        env.events().publish(
+            (symbol_short!("adm_prop"), admin),
+            new_admin,
+        );
+
+        Ok(())
     }
 
-    /// Mint tokens to an address (admin only, or authorized mint addresses)
-    /// Note: This is a placeholder - in production use Stellar Asset Contract with clawback/mint
-    pub fn mint(env: Env, to: Address, amount: i128) {
-        // i128 is a primitive type, no import needed
-        let admin: Address = env
+    /// Accept a pending admin transfer (called by the proposed admin)
+    pub fn accept_admin(env: Env) -> Result<(), Error> {
+        let admin = read_admin(&env)?;
+        let new_admin: Address = env
             .storage()
             .instance()
-            .get(&symbol_short!("admin"))
-            .unwrap();
+            .get(&symbol_short!("pend_adm"))
+            .ok_or(Error::NoPendingAdmin)?;
+        new_admin.require_auth();
+
+        env.storage().instance().set(&symbol_short!("admin"), &new_admin);
+        env.storage().instance().remove(&symbol_short!("pend_adm"));
+
+        env.events().publish(
+            (symbol_short!("adm_acc"), admin),
+            new_admin,
+        );
+
+        Ok(())
+    }
+
+    /// Admin: Cancel a pending admin transfer
+    pub fn cancel_admin_transfer(env: Env) -> Result<(), Error> {
+        let admin = read_admin(&env)?;
         admin.require_auth();
 
-        // In production, this would interact with Stellar Asset Contract
-        // For now, this is a placeholder for the quest platform integration
+        let pending: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("pend_adm"))
+            .ok_or(Error::NoPendingAdmin)?;
+        env.storage().instance().remove(&symbol_short!("pend_adm"));
+
+        env.events().publish(
+            (symbol_short!("adm_cncl"), admin),
+            pending,
+        );
+
+        Ok(())
+    }
+
+    /// Get current admin
+    pub fn get_admin(env: Env) -> Result<Address, Error> {
+        read_admin(&env)
+    }
+
+    /// Get the proposed admin awaiting acceptance
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("pend_adm"))
+    }
+}
+
+#[contractimpl]
+impl token::Interface for RewardToken {
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        bump_instance(&env);
+        read_allowance(&env, &from, &spender).amount
+    }
+
+    fn approve(env: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+        from.require_auth();
+        check_nonnegative_amount(&env, amount);
+
+        bump_instance(&env);
+        write_allowance(&env, &from, &spender, amount, expiration_ledger);
+
         env.events().publish(
-            (symbol_short!("minted"), to),
+            (symbol_short!("approve"), from, spender),
+            (amount, expiration_ledger),
+        );
+    }
+
+    fn balance(env: Env, id: Address) -> i128 {
+        bump_instance(&env);
+        read_balance(&env, &id)
+    }
+
+    fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        check_nonnegative_amount(&env, amount);
+
+        bump_instance(&env);
+        spend_balance(&env, &from, amount);
+        receive_balance(&env, &to, amount);
+
+        env.events().publish(
+            (symbol_short!("transfer"), from, to),
+            amount,
+        );
+    }
+
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+        check_nonnegative_amount(&env, amount);
+
+        bump_instance(&env);
+        spend_allowance(&env, &from, &spender, amount);
+        spend_balance(&env, &from, amount);
+        receive_balance(&env, &to, amount);
+
+        env.events().publish(
+            (symbol_short!("transfer"), from, to),
+            amount,
+        );
+    }
+
+    fn burn(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+        check_nonnegative_amount(&env, amount);
+
+        bump_instance(&env);
+        spend_balance(&env, &from, amount);
+        reduce_supply(&env, amount);
+
+        env.events().publish(
+            (symbol_short!("burn"), from),
+            amount,
+        );
+    }
+
+    fn burn_from(env: Env, spender: Address, from: Address, amount: i128) {
+        spender.require_auth();
+        check_nonnegative_amount(&env, amount);
+
+        bump_instance(&env);
+        spend_allowance(&env, &from, &spender, amount);
+        spend_balance(&env, &from, amount);
+        reduce_supply(&env, amount);
+
+        env.events().publish(
+            (symbol_short!("burn"), from),
             amount,
         );
     }
 
+    fn decimals(_env: Env) -> u32 {
+        DECIMALS
+    }
+
     /// Get token metadata
-    pub fn name(env: Env) -> String {
+    fn name(env: Env) -> String {
         env.storage()
             .instance()
             .get(&symbol_short!("name"))
-            .unwrap()
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized))
     }
 
-    pub fn symbol(env: Env) -> String {
+    fn symbol(env: Env) -> String {
         env.storage()
             .instance()
             .get(&symbol_short!("symbol"))
-            .unwrap()
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized))
     }
 }
 
 #[cfg(test)]
 mod test;
-
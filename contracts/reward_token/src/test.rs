@@ -1,22 +1,240 @@
 #![cfg(test)]
 
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, String,
+};
 use super::*;
 
+fn setup<'a>(env: &Env, admin: &Address) -> RewardTokenClient<'a> {
+    let contract_id = env.register_contract(None, RewardToken);
+    let client = RewardTokenClient::new(env, &contract_id);
+    client.initialize(
+        admin,
+        &String::from_str(env, "Quest Reward Token"),
+        &String::from_str(env, "QRT"),
+    );
+    client
+}
+
 #[test]
 fn test_initialize() {
     let env = Env::default();
+    env.mock_all_auths();
+
     let admin = Address::generate(&env);
-    
+
     let contract_id = env.register_contract(None, RewardToken);
     let client = RewardTokenClient::new(&env, &contract_id);
-    
+
     let name = String::from_str(&env, "Quest Reward Token");
     let symbol = String::from_str(&env, "QRT");
-    
+
     client.initialize(&admin, &name, &symbol);
-    
+
     assert_eq!(client.name(), name);
     assert_eq!(client.symbol(), symbol);
+    assert_eq!(client.decimals(), 7);
+    assert_eq!(client.total_supply(), 0);
+}
+
+#[test]
+fn test_mint_and_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let client = setup(&env, &admin);
+
+    client.mint(&user1, &1000);
+    assert_eq!(client.balance(&user1), 1000);
+    assert_eq!(client.total_supply(), 1000);
+
+    client.transfer(&user1, &user2, &600);
+    assert_eq!(client.balance(&user1), 400);
+    assert_eq!(client.balance(&user2), 600);
+    assert_eq!(client.total_supply(), 1000);
+}
+
+#[test]
+fn test_allowance_and_transfer_from() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let receiver = Address::generate(&env);
+    let client = setup(&env, &admin);
+
+    client.mint(&owner, &1000);
+    client.approve(&owner, &spender, &500, &200);
+    assert_eq!(client.allowance(&owner, &spender), 500);
+
+    client.transfer_from(&spender, &owner, &receiver, &300);
+    assert_eq!(client.balance(&owner), 700);
+    assert_eq!(client.balance(&receiver), 300);
+    assert_eq!(client.allowance(&owner, &spender), 200);
+}
+
+#[test]
+fn test_allowance_expires() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let client = setup(&env, &admin);
+
+    client.mint(&owner, &1000);
+    let expiration = env.ledger().sequence() + 100;
+    client.approve(&owner, &spender, &500, &expiration);
+
+    env.ledger().with_mut(|li| li.sequence_number = expiration);
+    assert_eq!(client.allowance(&owner, &spender), 500);
+
+    env.ledger().with_mut(|li| li.sequence_number = expiration + 1);
+    assert_eq!(client.allowance(&owner, &spender), 0);
+}
+
+#[test]
+fn test_burn_and_burn_from() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let client = setup(&env, &admin);
+
+    client.mint(&owner, &1000);
+    client.burn(&owner, &100);
+    assert_eq!(client.balance(&owner), 900);
+    assert_eq!(client.total_supply(), 900);
+
+    client.approve(&owner, &spender, &300, &200);
+    client.burn_from(&spender, &owner, &300);
+    assert_eq!(client.balance(&owner), 600);
+    assert_eq!(client.allowance(&owner, &spender), 0);
+    assert_eq!(client.total_supply(), 600);
+}
+
+#[test]
+fn test_token_errors() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let client = setup(&env, &admin);
+
+    client.mint(&owner, &100);
+    assert_eq!(client.try_mint(&owner, &-1), Err(Ok(Error::NegativeAmount)));
+    assert_eq!(client.try_mint(&owner, &i128::MAX), Err(Ok(Error::Overflow)));
+    assert_eq!(
+        client.try_transfer(&owner, &spender, &101),
+        Err(Ok(Error::InsufficientBalance.into()))
+    );
+    assert_eq!(
+        client.try_transfer(&owner, &spender, &-1),
+        Err(Ok(Error::NegativeAmount.into()))
+    );
+
+    env.ledger().with_mut(|li| li.sequence_number = 100);
+    assert_eq!(
+        client.try_approve(&owner, &spender, &50, &99),
+        Err(Ok(Error::ExpirationInPast.into()))
+    );
+
+    client.approve(&owner, &spender, &50, &110);
+    assert_eq!(
+        client.try_transfer_from(&spender, &owner, &spender, &51),
+        Err(Ok(Error::InsufficientAllowance.into()))
+    );
+
+    // Expired allowances can no longer be spent
+    env.ledger().with_mut(|li| li.sequence_number = 111);
+    assert_eq!(
+        client.try_burn_from(&spender, &owner, &10),
+        Err(Ok(Error::InsufficientAllowance.into()))
+    );
+}
+
+#[test]
+fn test_uninitialized_errors() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, RewardToken);
+    let client = RewardTokenClient::new(&env, &contract_id);
+
+    assert_eq!(
+        client.try_mint(&Address::generate(&env), &100),
+        Err(Ok(Error::NotInitialized))
+    );
+    assert_eq!(client.try_name(), Err(Ok(Error::NotInitialized.into())));
+}
+
+#[test]
+fn test_initialize_only_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    let name = String::from_str(&env, "Quest Reward Token");
+    let symbol = String::from_str(&env, "QRT");
+
+    let contract_id = env.register_contract(None, RewardToken);
+    let client = RewardTokenClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &name, &symbol);
+    assert_eq!(env.auths()[0].0, admin);
+
+    assert_eq!(
+        client.try_initialize(&attacker, &name, &symbol),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    assert_eq!(client.get_admin(), admin);
 }
 
+#[test]
+fn test_two_step_admin_handover() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let other = Address::generate(&env);
+    let name = String::from_str(&env, "Quest Reward Token");
+    let symbol = String::from_str(&env, "QRT");
+
+    let contract_id = env.register_contract(None, RewardToken);
+    let client = RewardTokenClient::new(&env, &contract_id);
+
+    assert_eq!(client.try_propose_admin(&new_admin), Err(Ok(Error::NotInitialized)));
+    client.initialize(&admin, &name, &symbol);
+    assert_eq!(client.try_accept_admin(), Err(Ok(Error::NoPendingAdmin)));
+
+    client.propose_admin(&new_admin);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+    assert_eq!(client.get_admin(), admin);
+
+    client.accept_admin();
+    assert_eq!(env.auths()[0].0, new_admin);
+    assert_eq!(client.get_admin(), new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+
+    client.propose_admin(&other);
+    assert_eq!(env.auths()[0].0, new_admin);
+    client.cancel_admin_transfer();
+    assert_eq!(client.get_pending_admin(), None);
+    assert_eq!(client.try_accept_admin(), Err(Ok(Error::NoPendingAdmin)));
+    assert_eq!(client.try_cancel_admin_transfer(), Err(Ok(Error::NoPendingAdmin)));
+    assert_eq!(client.get_admin(), new_admin);
+}
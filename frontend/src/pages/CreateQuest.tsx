@@ -2,6 +2,7 @@
 import { useState } from 'react'
 import { useFreighter } from '../hooks/useFreighter'
 import { useNavigate } from 'react-router-dom'
+import { nativeToScVal, xdr } from '@stellar/stellar-sdk'
 import { ContractTransactionService } from '../services/contractTransactionService'
 
 const QUEST_PLATFORM_CONTRACT_ID = import.meta.env.VITE_QUEST_PLATFORM_CONTRACT_ID || ''
@@ -18,6 +19,8 @@ export default function CreateQuest() {
     badgeId: '',
     expiresAt: '',
     maxCompletions: '',
+    prerequisites: '',
+    keepDraft: false,
   })
   const [submitting, setSubmitting] = useState(false)
   const [error, setError] = useState<string | null>(null)
@@ -44,16 +47,27 @@ export default function CreateQuest() {
       const expiresAt = formData.expiresAt 
         ? Math.floor(Date.now() / 1000) + (parseInt(formData.expiresAt) * 24 * 60 * 60)
         : null // null = None for Option<u64>
-      const maxCompletions = formData.maxCompletions 
-        ? parseInt(formData.maxCompletions) 
-        : null // null = None for Option<i128>
+      // The contract escrows reward_amount * max_completions up front, so a cap is required
+      const maxCompletions = parseInt(formData.maxCompletions)
+      if (!Number.isInteger(maxCompletions) || maxCompletions < 1) {
+        setError('Max Completions must be a positive whole number')
+        return
+      }
       const badgeId = formData.badgeId && formData.badgeId.trim() !== '' 
         ? formData.badgeId.trim() 
         : null // null = None for Option<Symbol>
+      const prerequisites = xdr.ScVal.scvVec(
+        formData.prerequisites
+          .split(',')
+          .map((id) => id.trim())
+          .filter((id) => id !== '')
+          .map((id) => nativeToScVal(id, { type: 'symbol' }))
+      )
 
       // Build transaction arguments (matching contract signature exactly)
       // creator: Address, quest_id: Symbol, title: String, description: String,
-      // reward_amount: i128, badge_id: Option<Symbol>, expires_at: Option<u64>, max_completions: Option<i128>
+      // reward_amount: i128, badge_id: Option<Symbol>, expires_at: Option<u64>, max_completions: Option<i128>,
+      // prerequisites: Vec<Symbol>
       const args = [
         publicKey, // creator (Address as string)
         formData.questId.trim(), // quest_id (Symbol) - must be <= 9 chars
@@ -62,7 +76,8 @@ export default function CreateQuest() {
         rewardAmount, // reward_amount (i128)
         badgeId, // badge_id (Option<Symbol>) - null if empty
         expiresAt, // expires_at (Option<u64>) - null if empty
-        maxCompletions, // max_completions (Option<i128>) - null if empty
+        maxCompletions, // max_completions (Option<i128>) - always set, the contract rejects None
+        prerequisites, // prerequisites (Vec<Symbol>) - empty if none
       ]
 
       // Invoke contract
@@ -74,7 +89,18 @@ export default function CreateQuest() {
         publicKey
       )
 
-      alert(`Quest created successfully!\n\nTransaction Hash: ${txHash}\n\nYour quest is now on-chain!`)
+      // Quests start as drafts so gates can be configured before anyone can claim the escrow
+      if (formData.keepDraft) {
+        alert(`Quest created as a draft!\n\nTransaction Hash: ${txHash}\n\nCall activate_quest once review, verifier and eligibility are set.`)
+      } else {
+        const activateHash = await txService.invokeContract(
+          QUEST_PLATFORM_CONTRACT_ID,
+          'activate_quest',
+          [publicKey, formData.questId.trim()],
+          publicKey
+        )
+        alert(`Quest created successfully!\n\nTransaction Hash: ${activateHash}\n\nYour quest is now live on-chain!`)
+      }
       navigate('/')
     } catch (error: any) {
       console.error('Error creating quest:', error)
@@ -187,7 +213,7 @@ export default function CreateQuest() {
 
         <div>
           <label className="block text-sm font-semibold text-gray-700 mb-2">
-            Badge ID (optional, max 9 chars)
+            Badge Class ID (optional, max 9 chars)
           </label>
           <input
             type="text"
@@ -197,23 +223,39 @@ export default function CreateQuest() {
             placeholder="badge_id"
             maxLength={9}
           />
-          <p className="text-xs text-gray-500 mt-1">NFT badge to mint when quest is completed</p>
+          <p className="text-xs text-gray-500 mt-1">Badge class you issued on the Badge NFT contract, minted when the quest is completed</p>
+        </div>
+
+        <div>
+          <label className="block text-sm font-semibold text-gray-700 mb-2">
+            Prerequisite Quest IDs (optional)
+          </label>
+          <input
+            type="text"
+            value={formData.prerequisites}
+            onChange={(e) => setFormData({ ...formData, prerequisites: e.target.value })}
+            className="w-full px-4 py-2 border border-gray-300 rounded-lg focus:ring-2 focus:ring-purple-500 focus:border-transparent"
+            placeholder="intro, forest"
+          />
+          <p className="text-xs text-gray-500 mt-1">Comma-separated existing quests a user must complete first</p>
         </div>
 
         <div className="grid grid-cols-2 gap-4">
           <div>
             <label className="block text-sm font-semibold text-gray-700 mb-2">
-              Max Completions
+              Max Completions *
             </label>
             <input
               type="number"
+              required
               min="1"
+              step="1"
               value={formData.maxCompletions}
               onChange={(e) => setFormData({ ...formData, maxCompletions: e.target.value })}
               className="w-full px-4 py-2 border border-gray-300 rounded-lg focus:ring-2 focus:ring-purple-500 focus:border-transparent"
               placeholder="100"
             />
-            <p className="text-xs text-gray-500 mt-1">Leave empty for unlimited</p>
+            <p className="text-xs text-gray-500 mt-1">Rewards for every completion are escrowed when the quest is created</p>
           </div>
 
           <div>
@@ -232,10 +274,19 @@ export default function CreateQuest() {
           </div>
         </div>
 
+        <label className="flex items-center gap-2 text-sm text-gray-700">
+          <input
+            type="checkbox"
+            checked={formData.keepDraft}
+            onChange={(e) => setFormData({ ...formData, keepDraft: e.target.checked })}
+          />
+          Keep as draft to set up review, a verifier or eligibility before going live
+        </label>
+
         <div className="bg-yellow-50 border border-yellow-200 rounded-lg p-4">
           <p className="text-sm text-yellow-800">
-            <strong>Note:</strong> Creating a quest requires a transaction on Stellar testnet.
-            You'll need to approve the transaction in Freighter wallet.
+            <strong>Note:</strong> Creating a quest requires a transaction on Stellar testnet, and a second one to activate it.
+            You'll need to approve the transactions in Freighter wallet.
           </p>
         </div>
 
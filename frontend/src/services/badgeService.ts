@@ -9,6 +9,7 @@ import {
   scValToNative,
   TransactionBuilder,
   Account,
+  xdr,
 } from '@stellar/stellar-sdk'
 
 const RPC_URL = import.meta.env.VITE_RPC_URL || 'https://soroban-testnet.stellar.org'
@@ -16,10 +17,36 @@ const BADGE_NFT_CONTRACT_ID = import.meta.env.VITE_BADGE_NFT_CONTRACT_ID || ''
 
 export interface Badge {
   id: string
-  quest_id: string
+  class_id: string
+  serial: number
+  quest_id?: string
   owner: string
   minted_at: string
-  metadata: string
+  revoked: boolean
+  valid_until?: string
+}
+
+export type Rarity = 'Common' | 'Uncommon' | 'Rare' | 'Epic' | 'Legendary'
+
+export interface BadgeMetadata {
+  name: string
+  description: string
+  image_uri: string
+  rarity: Rarity
+  attributes: Record<string, string>
+}
+
+export interface BadgeClass {
+  id: string
+  metadata: BadgeMetadata
+  metadata_frozen: boolean
+  issuer: string
+  max_supply?: number
+  supply: number
+  minted: number
+  soulbound: boolean
+  validity_period?: string
+  trusted: boolean
 }
 
 export class BadgeService {
@@ -36,6 +63,9 @@ export class BadgeService {
       const contract = new Contract(this.contractId)
       
       const scArgs = args.map(arg => {
+        if (arg instanceof xdr.ScVal) {
+          return arg
+        }
         if (typeof arg === 'string') {
           try {
             return Address.fromString(arg).toScVal()
@@ -88,7 +118,7 @@ export class BadgeService {
 
   async getBadge(badgeId: string): Promise<Badge | null> {
     try {
-      const result = await this.callContract('get_badge', badgeId)
+      const result = await this.callContract('get_badge', this.badgeIdArg(badgeId))
       return result ? this.parseBadge(result) : null
     } catch (error) {
       console.error('Error getting badge:', error)
@@ -96,19 +126,71 @@ export class BadgeService {
     }
   }
 
+  // Pages through badges_of, the contract only returns bounded pages
   async getUserBadges(userAddress: string): Promise<string[]> {
+    const total = await this.balanceOf(userAddress)
+    const badges: string[] = []
+    for (let offset = 0; offset < total; offset += 50) {
+      const page = await this.badgesOf(userAddress, offset, 50)
+      if (page.length === 0) break
+      badges.push(...page)
+    }
+    return badges
+  }
+
+  async badgesOf(userAddress: string, offset = 0, limit = 50): Promise<string[]> {
+    try {
+      const result = await this.callContract(
+        'badges_of',
+        userAddress,
+        nativeToScVal(offset, { type: 'u32' }),
+        nativeToScVal(limit, { type: 'u32' })
+      )
+      return Array.isArray(result) ? result.map((id: bigint) => id.toString()) : []
+    } catch (error) {
+      console.error('Error getting user badges page:', error)
+      return []
+    }
+  }
+
+  async balanceOf(userAddress: string): Promise<number> {
+    try {
+      const result = await this.callContract('balance_of', userAddress)
+      return Number(result) || 0
+    } catch (error) {
+      console.error('Error getting badge balance:', error)
+      return 0
+    }
+  }
+
+  async getUserValidBadges(userAddress: string, offset = 0, limit = 50): Promise<string[]> {
     try {
-      const result = await this.callContract('get_user_badges', userAddress)
-      return Array.isArray(result) ? result : []
+      const result = await this.callContract(
+        'get_user_valid_badges',
+        userAddress,
+        nativeToScVal(offset, { type: 'u32' }),
+        nativeToScVal(limit, { type: 'u32' })
+      )
+      return Array.isArray(result) ? result.map((id: bigint) => id.toString()) : []
     } catch (error) {
-      console.error('Error getting user badges:', error)
+      console.error('Error getting valid user badges:', error)
       return []
     }
   }
 
+  async isValid(badgeId: string): Promise<boolean> {
+    try {
+      const result = await this.callContract('is_valid', this.badgeIdArg(badgeId))
+      return Boolean(result)
+    } catch (error) {
+      console.error('Error checking badge validity:', error)
+      return false
+    }
+  }
+
   async ownerOf(badgeId: string): Promise<string | null> {
     try {
-      const result = await this.callContract('owner_of', badgeId)
+      const result = await this.callContract('owner_of', this.badgeIdArg(badgeId))
       return result ? result.toString() : null
     } catch (error) {
       console.error('Error getting badge owner:', error)
@@ -116,6 +198,17 @@ export class BadgeService {
     }
   }
 
+  // Address renting the badge, null once the rental has expired
+  async userOf(badgeId: string): Promise<string | null> {
+    try {
+      const result = await this.callContract('user_of', this.badgeIdArg(badgeId))
+      return result ? result.toString() : null
+    } catch (error) {
+      console.error('Error getting badge user:', error)
+      return null
+    }
+  }
+
   async totalBadges(): Promise<number> {
     try {
       const result = await this.callContract('total_badges')
@@ -126,13 +219,92 @@ export class BadgeService {
     }
   }
 
+  async getClass(classId: string): Promise<BadgeClass | null> {
+    try {
+      const result = await this.callContract('get_class', nativeToScVal(classId, { type: 'symbol' }))
+      return result ? this.parseClass(result) : null
+    } catch (error) {
+      console.error('Error getting badge class:', error)
+      return null
+    }
+  }
+
+  async tokenUri(badgeId: string): Promise<string | null> {
+    try {
+      const result = await this.callContract('token_uri', this.badgeIdArg(badgeId))
+      return result ? result.toString() : null
+    } catch (error) {
+      console.error('Error getting token URI:', error)
+      return null
+    }
+  }
+
+  async getClassHolders(classId: string, offset = 0, limit = 50): Promise<string[]> {
+    try {
+      const result = await this.callContract(
+        'class_holders',
+        nativeToScVal(classId, { type: 'symbol' }),
+        nativeToScVal(offset, { type: 'u32' }),
+        nativeToScVal(limit, { type: 'u32' })
+      )
+      return Array.isArray(result) ? result.map((holder: any) => holder.toString()) : []
+    } catch (error) {
+      console.error('Error getting class holders:', error)
+      return []
+    }
+  }
+
+  // Badge IDs are u64 on-chain, kept as decimal strings here
+  private badgeIdArg(badgeId: string): xdr.ScVal {
+    return nativeToScVal(BigInt(badgeId), { type: 'u64' })
+  }
+
   private parseBadge(data: any): Badge {
     return {
       id: data.id?.toString() || '',
-      quest_id: data.quest_id?.toString() || '',
+      class_id: data.class_id?.toString() || '',
+      serial: Number(data.serial) || 0,
+      quest_id: data.quest_id?.toString(),
       owner: data.owner?.toString() || '',
       minted_at: data.minted_at?.toString() || '0',
-      metadata: data.metadata?.toString() || '{}',
+      revoked: Boolean(data.revoked),
+      valid_until: data.valid_until?.toString(),
+    }
+  }
+
+  // Unit enum variants come back as a single-element array, e.g. ['Rare']
+  private parseMetadata(data: any): BadgeMetadata {
+    const attributes: Record<string, string> = {}
+    if (data?.attributes instanceof Map) {
+      data.attributes.forEach((value: any, key: any) => {
+        attributes[key.toString()] = value.toString()
+      })
+    } else if (data?.attributes) {
+      Object.entries(data.attributes).forEach(([key, value]) => {
+        attributes[key] = String(value)
+      })
+    }
+    return {
+      name: data?.name?.toString() || '',
+      description: data?.description?.toString() || '',
+      image_uri: data?.image_uri?.toString() || '',
+      rarity: (Array.isArray(data?.rarity) ? data.rarity[0] : data?.rarity) || 'Common',
+      attributes,
+    }
+  }
+
+  private parseClass(data: any): BadgeClass {
+    return {
+      id: data.id?.toString() || '',
+      metadata: this.parseMetadata(data.metadata),
+      metadata_frozen: Boolean(data.metadata_frozen),
+      issuer: data.issuer?.toString() || '',
+      max_supply: data.max_supply != null ? Number(data.max_supply) : undefined,
+      supply: Number(data.supply) || 0,
+      minted: Number(data.minted) || 0,
+      soulbound: Boolean(data.soulbound),
+      validity_period: data.validity_period?.toString(),
+      trusted: Boolean(data.trusted),
     }
   }
 }
@@ -36,8 +36,14 @@ export class ContractTransactionService {
       // Convert args to ScVal
       // For create_quest function signature:
       // creator: Address, quest_id: Symbol, title: String, description: String,
-      // reward_amount: i128, badge_id: Option<Symbol>, expires_at: Option<u64>, max_completions: Option<i128>
+      // reward_amount: i128, badge_id: Option<Symbol>, expires_at: Option<u64>, max_completions: Option<i128>,
+      // prerequisites: Vec<Symbol>
       const scArgs = args.map((arg, index) => {
+        // Pre-built values are passed through as-is
+        if (arg instanceof xdr.ScVal) {
+          return arg
+        }
+
         // Handle Option types (args at index 5, 6, 7 are Option types)
         if (arg === undefined || arg === null) {
           // None value for Option types
@@ -9,7 +9,8 @@ import {
   scValToNative,
   TransactionBuilder,
   Account,
-  BASE_FEE
+  BASE_FEE,
+  xdr
 } from '@stellar/stellar-sdk'
 const RPC_URL = import.meta.env.VITE_RPC_URL || 'https://soroban-testnet.stellar.org'
 const QUEST_PLATFORM_CONTRACT_ID = import.meta.env.VITE_QUEST_PLATFORM_CONTRACT_ID || ''
@@ -27,15 +28,42 @@ export interface Quest {
   expires_at?: string
   max_completions?: string
   current_completions: string
+  review_required: boolean
+  prerequisites: string[]
+}
+
+export interface QuestChainNode {
+  quest_id: string
+  prerequisites: string[]
+  depth: number
+}
+
+export interface Submission {
+  user: string
+  quest_id: string
+  proof_uri: string
+  status: string
+  submitted_at: string
+  reviewed_by?: string
+  reviewed_at?: string
 }
 
 export interface QuestCompletion {
   user: string
   quest_id: string
   completed_at: string
+  reward_amount: string
   reward_claimed: boolean
 }
 
+export interface UserStats {
+  total_completions: number
+  total_rewards: string
+}
+
+// Proof of eligibility for gated quests; omit it for open and list-based quests
+export type EligibilityProof = { merkle: string[] } | { badgeId: string }
+
 export class QuestService {
   private contractId: string
   private rpc: SorobanRpc.Server
@@ -51,6 +79,9 @@ export class QuestService {
       
       // Convert args to ScVals
       const scArgs = args.map(arg => {
+        if (arg instanceof xdr.ScVal) {
+          return arg
+        }
         if (typeof arg === 'string') {
           // Try Address first, then Symbol/String
           try {
@@ -112,9 +143,13 @@ export class QuestService {
     }
   }
 
-  async getActiveQuests(): Promise<Quest[]> {
+  async getActiveQuests(offset = 0, limit = 50): Promise<Quest[]> {
     try {
-      const result = await this.callContract('get_active_quests')
+      const result = await this.callContract(
+        'get_active_quests',
+        nativeToScVal(offset, { type: 'u32' }),
+        nativeToScVal(limit, { type: 'u32' })
+      )
       return Array.isArray(result) ? result.map((q: any) => this.parseQuest(q)) : []
     } catch (error) {
       console.error('Error getting active quests:', error)
@@ -122,6 +157,51 @@ export class QuestService {
     }
   }
 
+  async getQuestChain(questId: string): Promise<QuestChainNode[]> {
+    try {
+      const result = await this.callContract('get_quest_chain', questId)
+      return Array.isArray(result)
+        ? result.map((node: any) => ({
+            quest_id: node.quest_id?.toString() || '',
+            prerequisites: Array.isArray(node.prerequisites)
+              ? node.prerequisites.map((id: any) => id.toString())
+              : [],
+            depth: Number(node.depth) || 0,
+          }))
+        : []
+    } catch (error) {
+      console.error('Error getting quest chain:', error)
+      return []
+    }
+  }
+
+  // Merkle proofs are hex-encoded sibling hashes; badge gates take a badge the user owns or rents
+  private proofArg(proof?: EligibilityProof): xdr.ScVal {
+    if (proof && 'merkle' in proof) {
+      const path = proof.merkle.map((hash) =>
+        nativeToScVal(Uint8Array.from(hash.match(/../g) ?? [], (byte) => parseInt(byte, 16)))
+      )
+      return xdr.ScVal.scvVec([nativeToScVal('Merkle', { type: 'symbol' }), xdr.ScVal.scvVec(path)])
+    }
+    if (proof && 'badgeId' in proof) {
+      return xdr.ScVal.scvVec([
+        nativeToScVal('Badge', { type: 'symbol' }),
+        nativeToScVal(proof.badgeId, { type: 'u64' }),
+      ])
+    }
+    return xdr.ScVal.scvVec([nativeToScVal('None', { type: 'symbol' })])
+  }
+
+  async isEligible(userAddress: string, questId: string, proof?: EligibilityProof): Promise<boolean> {
+    try {
+      const result = await this.callContract('is_eligible', userAddress, questId, this.proofArg(proof))
+      return result === true
+    } catch (error) {
+      console.error('Error checking eligibility:', error)
+      return false
+    }
+  }
+
   async hasCompleted(userAddress: string, questId: string): Promise<boolean> {
     try {
       const result = await this.callContract('has_completed', userAddress, questId)
@@ -132,26 +212,84 @@ export class QuestService {
     }
   }
 
-  async getUserCompletions(userAddress: string): Promise<string[]> {
+  async getUserCompletions(userAddress: string, offset = 0, limit = 50): Promise<string[]> {
+    const completions = await this.getUserCompletionRecords(userAddress, offset, limit)
+    return completions.map(completion => completion.quest_id)
+  }
+
+  async getUserCompletionRecords(userAddress: string, offset = 0, limit = 50): Promise<QuestCompletion[]> {
     try {
-      const result = await this.callContract('get_user_completions', userAddress)
-      return Array.isArray(result) ? result : []
+      const result = await this.callContract(
+        'get_user_completions',
+        userAddress,
+        nativeToScVal(offset, { type: 'u32' }),
+        nativeToScVal(limit, { type: 'u32' })
+      )
+      return Array.isArray(result)
+        ? result.map((c: any) => ({
+            user: c.user?.toString() || '',
+            quest_id: c.quest_id?.toString() || '',
+            completed_at: c.completed_at?.toString() || '0',
+            reward_amount: c.reward_amount?.toString() || '0',
+            reward_claimed: c.reward_claimed === true,
+          }))
+        : []
     } catch (error) {
       console.error('Error getting user completions:', error)
       return []
     }
   }
 
+  async getUserStats(userAddress: string): Promise<UserStats> {
+    try {
+      const result = await this.callContract('get_user_stats', userAddress)
+      return {
+        total_completions: Number(result?.total_completions) || 0,
+        total_rewards: result?.total_rewards?.toString() || '0',
+      }
+    } catch (error) {
+      console.error('Error getting user stats:', error)
+      return { total_completions: 0, total_rewards: '0' }
+    }
+  }
+
   async getLeaderboard(): Promise<Array<[string, number]>> {
     try {
       const result = await this.callContract('get_leaderboard')
-      return Array.isArray(result) ? result : []
+      return Array.isArray(result)
+        ? result.map((entry: any) => [entry.user?.toString() || '', Number(entry.completions) || 0])
+        : []
     } catch (error) {
       console.error('Error getting leaderboard:', error)
       return []
     }
   }
 
+  async getSubmission(userAddress: string, questId: string): Promise<Submission | null> {
+    try {
+      const result = await this.callContract('get_submission', userAddress, questId)
+      return result ? this.parseSubmission(result) : null
+    } catch (error) {
+      console.error('Error getting submission:', error)
+      return null
+    }
+  }
+
+  async getSubmissions(questId: string, offset = 0, limit = 50): Promise<Submission[]> {
+    try {
+      const result = await this.callContract(
+        'get_submissions',
+        questId,
+        nativeToScVal(offset, { type: 'u32' }),
+        nativeToScVal(limit, { type: 'u32' })
+      )
+      return Array.isArray(result) ? result.map((s: any) => this.parseSubmission(s)) : []
+    } catch (error) {
+      console.error('Error getting submissions:', error)
+      return []
+    }
+  }
+
   async getQuestCount(): Promise<number> {
     try {
       const result = await this.callContract('get_quest_count')
@@ -176,11 +314,27 @@ export class QuestService {
       expires_at: data.expires_at?.toString(),
       max_completions: data.max_completions?.toString(),
       current_completions: data.current_completions?.toString() || '0',
+      review_required: data.review_required === true,
+      prerequisites: Array.isArray(data.prerequisites)
+        ? data.prerequisites.map((id: any) => id.toString())
+        : [],
+    }
+  }
+
+  private parseSubmission(data: any): Submission {
+    return {
+      user: data.user?.toString() || '',
+      quest_id: data.quest_id?.toString() || '',
+      proof_uri: data.proof_uri?.toString() || '',
+      status: data.status?.toString() || 'pending',
+      submitted_at: data.submitted_at?.toString() || '0',
+      reviewed_by: data.reviewed_by?.toString(),
+      reviewed_at: data.reviewed_at?.toString(),
     }
   }
 
   // Build transaction for completing a quest (to be signed by wallet)
-  async buildCompleteQuestTransaction(userAddress: string, questId: string): Promise<string> {
+  async buildCompleteQuestTransaction(userAddress: string, questId: string, proof?: EligibilityProof): Promise<string> {
     try {
       const contract = new Contract(this.contractId)
       
@@ -194,7 +348,7 @@ export class QuestService {
         fee: BASE_FEE,
         networkPassphrase: 'Test SDF Network ; September 2015',
       })
-        .addOperation(contract.call('complete_quest', userAddressScVal, questIdScVal))
+        .addOperation(contract.call('complete_quest', userAddressScVal, questIdScVal, this.proofArg(proof)))
         .setTimeout(30)
         .build()
 
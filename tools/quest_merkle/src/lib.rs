@@ -0,0 +1,169 @@
+//! Quest Merkle Allowlists
+//! Stellar Adventure Quest Platform - Off-chain Merkle tree builder
+//! Builds the sha256 root and proofs for quests gated by `Eligibility::MerkleRoot`
+//!
+//! Leaves and nodes are hashed exactly like QuestPlatform does on-chain:
+//! leaf = sha256(0x00 || XDR of the address as an ScVal)
+//! node = sha256(0x01 || lower child || higher child)
+
+use sha2::{Digest, Sha256};
+use std::{fmt, str::FromStr};
+use stellar_xdr::curr::{Limits, ScAddress, ScVal, WriteXdr};
+
+/// Domain prefix of leaf hashes, keeps leaves and nodes apart
+const LEAF_PREFIX: u8 = 0;
+/// Domain prefix of inner node hashes
+const NODE_PREFIX: u8 = 1;
+
+pub type Hash = [u8; 32];
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// A CSV line that is not a Stellar account or contract address, with its 1-based line number
+    InvalidAddress(usize, String),
+    /// The list holds no addresses
+    Empty,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidAddress(line, address) => {
+                write!(f, "line {}: invalid address {:?}", line, address)
+            }
+            Error::Empty => write!(f, "no addresses to build a tree from"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Hash an address ("G..." or "C...") into a leaf
+pub fn leaf_hash(address: &str) -> Option<Hash> {
+    let address = ScAddress::from_str(address).ok()?;
+    let xdr = ScVal::Address(address).to_xdr(Limits::none()).ok()?;
+
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(xdr);
+    Some(hasher.finalize().into())
+}
+
+/// Hash two children into their parent, children are sorted so proofs need no left/right flags
+pub fn node_hash(a: &Hash, b: &Hash) -> Hash {
+    let (low, high) = if a <= b { (a, b) } else { (b, a) };
+
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(low);
+    hasher.update(high);
+    hasher.finalize().into()
+}
+
+/// Check a proof the same way QuestPlatform does
+pub fn verify(root: &Hash, address: &str, proof: &[Hash]) -> bool {
+    match leaf_hash(address) {
+        Some(leaf) => proof.iter().fold(leaf, |hash, sibling| node_hash(&hash, sibling)) == *root,
+        None => false,
+    }
+}
+
+/// Read the first column of a CSV of addresses
+/// Blank lines and a header row are skipped
+pub fn parse_csv(csv: &str) -> Result<Vec<String>, Error> {
+    let mut addresses = Vec::new();
+
+    for (pos, line) in csv.lines().enumerate() {
+        let field = line.split(',').next().unwrap_or("").trim().trim_matches('"');
+        if field.is_empty() || (pos == 0 && field.eq_ignore_ascii_case("address")) {
+            continue;
+        }
+        if leaf_hash(field).is_none() {
+            return Err(Error::InvalidAddress(pos + 1, field.to_string()));
+        }
+        addresses.push(field.to_string());
+    }
+    Ok(addresses)
+}
+
+/// Merkle tree over a set of addresses
+/// Leaves are sorted and deduplicated, so the root does not depend on input order
+#[derive(Clone, Debug)]
+pub struct MerkleTree {
+    /// levels[0] holds the leaves, the last level holds the root
+    levels: Vec<Vec<Hash>>,
+}
+
+impl MerkleTree {
+    pub fn from_addresses<S: AsRef<str>>(addresses: &[S]) -> Result<Self, Error> {
+        let mut leaves = Vec::with_capacity(addresses.len());
+        for (pos, address) in addresses.iter().enumerate() {
+            let address = address.as_ref();
+            let leaf = leaf_hash(address)
+                .ok_or_else(|| Error::InvalidAddress(pos + 1, address.to_string()))?;
+            leaves.push(leaf);
+        }
+        if leaves.is_empty() {
+            return Err(Error::Empty);
+        }
+        leaves.sort_unstable();
+        leaves.dedup();
+
+        // An odd node out is carried up to the next level unchanged
+        let mut levels = vec![leaves];
+        while levels.last().unwrap().len() > 1 {
+            let level = levels.last().unwrap();
+            let next = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => node_hash(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+        Ok(MerkleTree { levels })
+    }
+
+    pub fn from_csv(csv: &str) -> Result<Self, Error> {
+        Self::from_addresses(&parse_csv(csv)?)
+    }
+
+    /// Root to set on the quest
+    pub fn root(&self) -> Hash {
+        self.levels.last().unwrap()[0]
+    }
+
+    /// Number of distinct addresses in the tree
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels[0].is_empty()
+    }
+
+    /// Sibling hashes from the address's leaf up to the root, None if the address is not listed
+    pub fn proof(&self, address: &str) -> Option<Vec<Hash>> {
+        let leaf = leaf_hash(address)?;
+        let mut pos = self.levels[0].binary_search(&leaf).ok()?;
+
+        let mut proof = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(pos ^ 1) {
+                proof.push(*sibling);
+            }
+            pos /= 2;
+        }
+        Some(proof)
+    }
+}
+
+/// Hex-encode a hash, e.g. to pass the root to the Stellar CLI
+pub fn to_hex(hash: &Hash) -> String {
+    hash.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,82 @@
+#![cfg(test)]
+
+use super::*;
+use stellar_xdr::curr::{AccountId, PublicKey, Uint256};
+
+fn account(seed: u8) -> String {
+    ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([seed; 32])))).to_string()
+}
+
+fn contract(seed: u8) -> String {
+    ScAddress::Contract(stellar_xdr::curr::Hash([seed; 32])).to_string()
+}
+
+#[test]
+fn test_every_listed_address_round_trips() {
+    for count in 1..=9u8 {
+        let addresses: Vec<String> = (0..count).map(account).collect();
+        let tree = MerkleTree::from_addresses(&addresses).unwrap();
+        assert_eq!(tree.len(), count as usize);
+
+        for address in &addresses {
+            let proof = tree.proof(address).unwrap();
+            assert!(verify(&tree.root(), address, &proof));
+        }
+    }
+}
+
+#[test]
+fn test_unlisted_and_tampered_proofs_fail() {
+    let addresses: Vec<String> = (0..5).map(account).collect();
+    let tree = MerkleTree::from_addresses(&addresses).unwrap();
+    let outsider = account(99);
+
+    assert_eq!(tree.proof(&outsider), None);
+    let proof = tree.proof(&addresses[0]).unwrap();
+    assert!(!verify(&tree.root(), &outsider, &proof));
+
+    let mut tampered = proof.clone();
+    tampered[0][0] ^= 1;
+    assert!(!verify(&tree.root(), &addresses[0], &tampered));
+    assert!(!verify(&tree.root(), &addresses[0], &proof[1..]));
+}
+
+#[test]
+fn test_csv_round_trip() {
+    let csv = format!(
+        "address,amount\n{},10\n\n\"{}\",20\n  {}  \n{}\n",
+        account(1),
+        contract(2),
+        account(3),
+        account(1),
+    );
+    let tree = MerkleTree::from_csv(&csv).unwrap();
+
+    // Duplicates collapse into one leaf and order does not matter
+    assert_eq!(tree.len(), 3);
+    let reordered = MerkleTree::from_addresses(&[account(3), contract(2), account(1)]).unwrap();
+    assert_eq!(tree.root(), reordered.root());
+
+    for address in [account(1), contract(2), account(3)] {
+        assert!(verify(&tree.root(), &address, &tree.proof(&address).unwrap()));
+    }
+}
+
+#[test]
+fn test_single_address_root_is_its_leaf() {
+    let tree = MerkleTree::from_addresses(&[account(7)]).unwrap();
+    assert_eq!(tree.root(), leaf_hash(&account(7)).unwrap());
+    assert_eq!(tree.proof(&account(7)), Some(Vec::new()));
+    assert_eq!(to_hex(&[0xab; 32]), "ab".repeat(32));
+}
+
+#[test]
+fn test_invalid_input() {
+    let csv = format!("{}\nnot-an-address\n", account(1));
+    assert_eq!(
+        MerkleTree::from_csv(&csv).unwrap_err(),
+        Error::InvalidAddress(2, "not-an-address".to_string())
+    );
+    assert_eq!(MerkleTree::from_csv("address\n\n").unwrap_err(), Error::Empty);
+    assert!(!verify(&[0; 32], "not-an-address", &[]));
+}